@@ -0,0 +1,105 @@
+use crate::ray::Ray;
+use crate::vec3::{Point3, Vec3};
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Aabb {
+    pub minimum: Point3,
+    pub maximum: Point3,
+}
+
+// constructor functions
+impl Aabb {
+    pub fn new(a: Point3, b: Point3) -> Aabb {
+        Aabb {
+            minimum: a,
+            maximum: b,
+        }
+    }
+
+    // build the smallest box containing every given point
+    pub fn from_points(points: &[Point3]) -> Aabb {
+        let mut minimum = Point3::new(f64::INFINITY, f64::INFINITY, f64::INFINITY);
+        let mut maximum = Point3::new(-f64::INFINITY, -f64::INFINITY, -f64::INFINITY);
+        for p in points {
+            for a in 0..3 {
+                minimum[a] = minimum[a].min(p[a]);
+                maximum[a] = maximum[a].max(p[a]);
+            }
+        }
+        Aabb { minimum, maximum }
+    }
+}
+
+// member functions
+impl Aabb {
+    pub fn min(&self) -> Point3 {
+        self.minimum
+    }
+
+    pub fn max(&self) -> Point3 {
+        self.maximum
+    }
+
+    pub fn corners(&self) -> [Point3; 8] {
+        let (a, b) = (self.minimum, self.maximum);
+        [
+            Point3::new(a.x(), a.y(), a.z()),
+            Point3::new(b.x(), a.y(), a.z()),
+            Point3::new(a.x(), b.y(), a.z()),
+            Point3::new(b.x(), b.y(), a.z()),
+            Point3::new(a.x(), a.y(), b.z()),
+            Point3::new(b.x(), a.y(), b.z()),
+            Point3::new(a.x(), b.y(), b.z()),
+            Point3::new(b.x(), b.y(), b.z()),
+        ]
+    }
+
+    pub fn contains(&self, p: Point3) -> bool {
+        (0..3).all(|a| self.minimum[a] <= p[a] && p[a] <= self.maximum[a])
+    }
+
+    // returns the parametric interval for which the ray is inside the box,
+    // clipped to [t_min, t_max]
+    pub fn hit_interval(&self, r: &Ray, t_min: f64, t_max: f64) -> Option<(f64, f64)> {
+        let mut t_min = t_min;
+        let mut t_max = t_max;
+        for a in 0..3 {
+            let inv_d = 1.0 / r.direction()[a];
+            let mut t0 = (self.minimum[a] - r.origin()[a]) * inv_d;
+            let mut t1 = (self.maximum[a] - r.origin()[a]) * inv_d;
+            if inv_d < 0.0 {
+                std::mem::swap(&mut t0, &mut t1);
+            }
+            // nan comparisons fall through so rays lying in a slab plane
+            // are not rejected
+            if t0 > t_min {
+                t_min = t0;
+            }
+            if t1 < t_max {
+                t_max = t1;
+            }
+            if t_max <= t_min {
+                return None;
+            }
+        }
+        Some((t_min, t_max))
+    }
+
+    pub fn hit(&self, r: &Ray, t_min: f64, t_max: f64) -> bool {
+        self.hit_interval(r, t_min, t_max).is_some()
+    }
+}
+
+pub fn surrounding_box(box0: Aabb, box1: Aabb) -> Aabb {
+    let small = Vec3::new(
+        box0.min().x().min(box1.min().x()),
+        box0.min().y().min(box1.min().y()),
+        box0.min().z().min(box1.min().z()),
+    );
+    let big = Vec3::new(
+        box0.max().x().max(box1.max().x()),
+        box0.max().y().max(box1.max().y()),
+        box0.max().z().max(box1.max().z()),
+    );
+    Aabb::new(small, big)
+}
@@ -0,0 +1,79 @@
+use crate::aabb::Aabb;
+use crate::color::luminance;
+use crate::hittable::{HitRecord, Hittable};
+use crate::ray::Ray;
+use crate::rtweekend::random_f64;
+use crate::texture::Texture;
+use crate::vec3::Point3;
+
+use std::sync::Arc;
+
+// most transparent surfaces a single ray may pass through
+const MAX_SKIPS: usize = 64;
+
+#[derive(Debug, Copy, Clone)]
+pub enum AlphaMode {
+    // solid where the opacity reaches the threshold, empty elsewhere
+    Threshold(f64),
+    // solid with probability equal to the opacity, for soft edges
+    Stochastic,
+}
+
+// cuts holes in an object where an opacity texture is low, e.g. leaves
+// or fences on quads. rejected hits are skipped inside `hit`, so rays
+// pass through without scattering or using up bounce depth
+pub struct AlphaMask {
+    pub object: Arc<dyn Hittable + Sync + Send>,
+    // luminance of the texture at the hit is its opacity
+    pub opacity: Arc<dyn Texture + Sync + Send>,
+    pub mode: AlphaMode,
+}
+
+// constructor functions
+impl AlphaMask {
+    pub fn new(
+        object: Arc<dyn Hittable + Sync + Send>,
+        opacity: Arc<dyn Texture + Sync + Send>,
+        mode: AlphaMode,
+    ) -> AlphaMask {
+        AlphaMask {
+            object,
+            opacity,
+            mode,
+        }
+    }
+}
+
+// member functions
+impl AlphaMask {
+    pub fn opacity_at(&self, u: f64, v: f64, p: &Point3) -> f64 {
+        luminance(self.opacity.value(u, v, p)).clamp(0.0, 1.0)
+    }
+
+    fn opaque(&self, rec: &HitRecord) -> bool {
+        let alpha = self.opacity_at(rec.u, rec.v, &rec.p);
+        match self.mode {
+            AlphaMode::Threshold(threshold) => alpha >= threshold,
+            AlphaMode::Stochastic => alpha > 0.0 && random_f64() < alpha,
+        }
+    }
+}
+
+impl Hittable for AlphaMask {
+    fn hit(&self, r: &Ray, t_min: f64, t_max: f64) -> Option<HitRecord> {
+        let mut t_start = t_min;
+        for _ in 0..MAX_SKIPS {
+            let rec = self.object.hit(r, t_start, t_max)?;
+            if self.opaque(&rec) {
+                return Some(rec);
+            }
+            // continue just past the rejected surface
+            t_start = rec.t + 1e-7 * rec.t.abs().max(1.0);
+        }
+        None
+    }
+
+    fn bounding_box(&self) -> Option<Aabb> {
+        self.object.bounding_box()
+    }
+}
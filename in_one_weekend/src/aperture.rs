@@ -0,0 +1,105 @@
+use crate::image::Image;
+use crate::rtweekend::{degrees_to_radians, random_f64, PI};
+use crate::vec3::*;
+
+// shape of the lens opening, which out of focus highlights take on.
+// samples are points in the lens plane, scaled by the camera's lens
+// radius
+pub enum Aperture {
+    Circular,
+    // regular polygon of straight blades, rotation in degrees
+    Polygon { blades: u32, rotation: f64 },
+    Mask(ApertureMask),
+}
+
+// constructor functions
+impl Aperture {
+    // none for fewer than three blades, which enclose no area
+    pub fn polygon(blades: u32, rotation: f64) -> Option<Aperture> {
+        if blades < 3 {
+            return None;
+        }
+        Some(Aperture::Polygon { blades, rotation })
+    }
+
+    // none for an image without any open pixel
+    pub fn mask(image: &Image) -> Option<Aperture> {
+        ApertureMask::new(image).map(Aperture::Mask)
+    }
+}
+
+// member functions
+impl Aperture {
+    pub fn sample(&self) -> Vec3 {
+        match self {
+            Aperture::Circular => random_in_unit_disk(),
+            Aperture::Polygon { blades, rotation } => sample_polygon(*blades, *rotation),
+            Aperture::Mask(mask) => mask.sample(),
+        }
+    }
+}
+
+// uniform point in a regular polygon inscribed in the unit circle: one of
+// its equal triangles around the center is picked, then a point in it
+fn sample_polygon(blades: u32, rotation: f64) -> Vec3 {
+    debug_assert!(blades >= 3, "aperture polygon needs three blades");
+    let k: u32 = ((random_f64() * blades as f64) as u32).min(blades - 1);
+    let step: f64 = 2.0 * PI / blades as f64;
+    let theta: f64 = degrees_to_radians(rotation) + k as f64 * step;
+    let a: Vec3 = Vec3::new(theta.cos(), theta.sin(), 0.0);
+    let b: Vec3 = Vec3::new((theta + step).cos(), (theta + step).sin(), 0.0);
+
+    // the center is the triangle's third corner
+    let (mut r1, mut r2) = (random_f64(), random_f64());
+    if r1 + r2 > 1.0 {
+        r1 = 1.0 - r1;
+        r2 = 1.0 - r2;
+    }
+    r1 * a + r2 * b
+}
+
+// grayscale image of the opening, sampled in proportion to its
+// brightness. the image fills the square around the unit disk, top up
+pub struct ApertureMask {
+    pub width: usize,
+    pub height: usize,
+    // running sum of pixel luminance, normalized to end at one
+    cdf: Vec<f64>,
+}
+
+impl ApertureMask {
+    // none for an empty or black image, which lets no light through
+    pub fn new(image: &Image) -> Option<ApertureMask> {
+        let mut cdf: Vec<f64> = Vec::with_capacity(image.width * image.height);
+        let mut total: f64 = 0.0;
+        for y in 0..image.height {
+            for x in 0..image.width {
+                total += image.luminance(x, y).max(0.0);
+                cdf.push(total);
+            }
+        }
+        if total <= 0.0 {
+            return None;
+        }
+        for c in cdf.iter_mut() {
+            *c /= total;
+        }
+        Some(ApertureMask {
+            width: image.width,
+            height: image.height,
+            cdf,
+        })
+    }
+
+    pub fn sample(&self) -> Vec3 {
+        let xi: f64 = random_f64();
+        let idx: usize = self
+            .cdf
+            .partition_point(|&c| c <= xi)
+            .min(self.cdf.len() - 1);
+        let (x, y) = (idx % self.width, idx / self.width);
+        let s: f64 = (x as f64 + random_f64()) / self.width as f64;
+        let t: f64 = (y as f64 + random_f64()) / self.height as f64;
+        Vec3::new(2.0 * s - 1.0, 1.0 - 2.0 * t, 0.0)
+    }
+}
@@ -1,9 +1,96 @@
+use crate::aperture::Aperture;
 use crate::ray::Ray;
 use crate::rtweekend::degrees_to_radians;
 use crate::vec3::*;
 
+// maps film coordinates (s, t) in [0, 1], from the lower left corner,
+// to a ray into the scene
+pub trait Camera {
+    fn get_ray(&self, s: f64, t: f64) -> Ray;
+
+    // whether (s, t) sees the scene at all, e.g. not outside the image
+    // circle of a fisheye. the film stays black where it does not
+    fn in_frame(&self, _s: f64, _t: f64) -> bool {
+        true
+    }
+
+    // scale applied to the light reaching the film
+    fn exposure(&self) -> f64 {
+        1.0
+    }
+}
+
+// a real camera body and lens. lengths on the sensor are in millimeters,
+// the shutter is in seconds and scene units are taken to be meters
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct PhysicalSettings {
+    pub sensor_width: f64,
+    pub sensor_height: f64,
+    pub focal_length: f64,
+    pub f_number: f64,
+    pub shutter: f64,
+    pub iso: f64,
+}
+
+// constructor functions
+impl PhysicalSettings {
+    pub fn new(
+        sensor_width: f64,
+        sensor_height: f64,
+        focal_length: f64,
+        f_number: f64,
+        shutter: f64,
+        iso: f64,
+    ) -> PhysicalSettings {
+        PhysicalSettings {
+            sensor_width,
+            sensor_height,
+            focal_length,
+            f_number,
+            shutter,
+            iso,
+        }
+    }
+
+    // 36 x 24 mm sensor
+    pub fn full_frame(
+        focal_length: f64,
+        f_number: f64,
+        shutter: f64,
+        iso: f64,
+    ) -> PhysicalSettings {
+        PhysicalSettings::new(36.0, 24.0, focal_length, f_number, shutter, iso)
+    }
+}
+
+// member functions
+impl PhysicalSettings {
+    // vertical field of view in degrees
+    pub fn vfov(&self) -> f64 {
+        2.0 * (self.sensor_height / (2.0 * self.focal_length))
+            .atan()
+            .to_degrees()
+    }
+
+    pub fn aspect_ratio(&self) -> f64 {
+        self.sensor_width / self.sensor_height
+    }
+
+    // entrance pupil diameter in scene units
+    pub fn aperture(&self) -> f64 {
+        self.focal_length / self.f_number / 1000.0
+    }
+
+    // film exposure relative to the sunny 16 rule, f/16 at a shutter of
+    // 1 / iso, which is correct for a sky of radiance around one
+    pub fn exposure(&self) -> f64 {
+        self.shutter * self.iso * (16.0 / self.f_number).powi(2)
+    }
+}
+
+// pinhole or thin lens camera with a vertical field of view in degrees
 #[allow(dead_code)]
-pub struct Camera {
+pub struct PerspectiveCamera {
     origin: Point3,
     lower_left_corner: Point3,
     horizontal: Vec3,
@@ -12,9 +99,11 @@ pub struct Camera {
     v: Vec3,
     w: Vec3,
     lens_radius: f64,
+    aperture: Aperture,
+    exposure: f64,
 }
 
-impl Camera {
+impl PerspectiveCamera {
     pub fn new(
         lookfrom: Point3,
         lookat: Point3,
@@ -23,7 +112,7 @@ impl Camera {
         aspect_ratio: f64,
         aperture: f64,
         focus_dist: f64,
-    ) -> Camera {
+    ) -> PerspectiveCamera {
         let theta: f64 = degrees_to_radians(vfov);
         let h: f64 = (theta / 2.0).tan();
         let viewport_height: f64 = 2.0 * h;
@@ -38,7 +127,7 @@ impl Camera {
         let vertical = focus_dist * viewport_height * v;
         let lower_left_corner = origin - horizontal / 2.0 - vertical / 2.0 - focus_dist * w;
         let lens_radius = aperture / 2.0;
-        Camera {
+        PerspectiveCamera {
             origin,
             lower_left_corner,
             horizontal,
@@ -47,17 +136,47 @@ impl Camera {
             v,
             w,
             lens_radius,
+            aperture: Aperture::Circular,
+            exposure: 1.0,
         }
     }
 
-    pub fn get_ray(&self, s: f64, t: f64) -> Ray {
-        let rd: Vec3 = self.lens_radius * random_in_unit_disk();
-        let offset: Vec3 = self.u * rd.x() + self.v * rd.y();
+    // field of view, lens size and exposure from a real camera, focused
+    // at focus_dist
+    pub fn physical(
+        lookfrom: Point3,
+        lookat: Point3,
+        vup: Vec3,
+        settings: &PhysicalSettings,
+        focus_dist: f64,
+    ) -> PerspectiveCamera {
+        PerspectiveCamera {
+            exposure: settings.exposure(),
+            ..PerspectiveCamera::new(
+                lookfrom,
+                lookat,
+                vup,
+                settings.vfov(),
+                settings.aspect_ratio(),
+                settings.aperture(),
+                focus_dist,
+            )
+        }
+    }
 
-        Ray::new(
-            self.origin + offset,
-            self.lower_left_corner + s * self.horizontal + t * self.vertical - self.origin - offset,
-        )
+    // shapes the lens opening, and with it the bokeh, within the
+    // aperture's diameter
+    pub fn with_aperture(mut self, aperture: Aperture) -> PerspectiveCamera {
+        self.aperture = aperture;
+        self
+    }
+
+    // moves the center of projection by offset while keeping the window
+    // on the focus plane in place, skewing the frustum off axis. used for
+    // the eyes of a parallel stereo rig
+    pub fn with_eye_offset(mut self, offset: Vec3) -> PerspectiveCamera {
+        self.origin += offset;
+        self
     }
 
     #[allow(dead_code)]
@@ -80,3 +199,81 @@ impl Camera {
         self.vertical
     }
 }
+
+impl Camera for PerspectiveCamera {
+    fn get_ray(&self, s: f64, t: f64) -> Ray {
+        let rd: Vec3 = self.lens_radius * self.aperture.sample();
+        let offset: Vec3 = self.u * rd.x() + self.v * rd.y();
+
+        Ray::new(
+            self.origin + offset,
+            self.lower_left_corner + s * self.horizontal + t * self.vertical - self.origin - offset,
+        )
+    }
+
+    fn exposure(&self) -> f64 {
+        self.exposure
+    }
+}
+
+// parallel projection for technical and isometric views. view_width is
+// the width of the visible region in scene units, and everything is in
+// focus
+#[allow(dead_code)]
+pub struct OrthographicCamera {
+    lower_left_corner: Point3,
+    horizontal: Vec3,
+    vertical: Vec3,
+    w: Vec3,
+}
+
+impl OrthographicCamera {
+    pub fn new(
+        lookfrom: Point3,
+        lookat: Point3,
+        vup: Vec3,
+        view_width: f64,
+        aspect_ratio: f64,
+    ) -> OrthographicCamera {
+        let view_height: f64 = view_width / aspect_ratio;
+
+        let w = unit_vector(lookfrom - lookat);
+        let u = unit_vector(cross(&vup, &w));
+        let v = cross(&w, &u);
+
+        let horizontal = view_width * u;
+        let vertical = view_height * v;
+        let lower_left_corner = lookfrom - horizontal / 2.0 - vertical / 2.0;
+        OrthographicCamera {
+            lower_left_corner,
+            horizontal,
+            vertical,
+            w,
+        }
+    }
+
+    #[allow(dead_code)]
+    pub fn lower_left_corner(&self) -> Point3 {
+        self.lower_left_corner
+    }
+
+    #[allow(dead_code)]
+    pub fn horizontal(&self) -> Vec3 {
+        self.horizontal
+    }
+
+    #[allow(dead_code)]
+    pub fn vertical(&self) -> Vec3 {
+        self.vertical
+    }
+}
+
+impl Camera for OrthographicCamera {
+    // every ray leaves the view plane in the viewing direction
+    fn get_ray(&self, s: f64, t: f64) -> Ray {
+        Ray::new(
+            self.lower_left_corner + s * self.horizontal + t * self.vertical,
+            -self.w,
+        )
+    }
+}
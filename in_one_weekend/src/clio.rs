@@ -6,17 +6,7 @@ pub fn get_thread_parameters() -> ThreadParameters {
     // remove flags from the argument list
     let args: Vec<String> = args
         .into_iter()
-        .filter(|x| {
-            if x.len() > 1 {
-                if (&x[0..2]) == "--" {
-                    false
-                } else {
-                    true
-                }
-            } else {
-                true
-            }
-        })
+        .filter(|x| if x.len() > 1 { &x[0..2] != "--" } else { true })
         .collect();
 
     if args.len() == 1 {
@@ -43,3 +33,8 @@ pub fn get_thread_parameters() -> ThreadParameters {
         }
     }
 }
+
+// whether a `--` flag was passed on the commandline
+pub fn has_flag(flag: &str) -> bool {
+    env::args().any(|x| x == flag)
+}
@@ -19,6 +19,11 @@ pub fn color_to_string(pixel_color: Color, samples_per_pixel: i32) -> String {
     )
 }
 
+// rec. 709 luminance, for grayscale uses of colors
+pub fn luminance(c: Color) -> f64 {
+    0.2126 * c.x() + 0.7152 * c.y() + 0.0722 * c.z()
+}
+
 pub fn write_color(pixel_color: Color, samples_per_pixel: i32) {
     println!("{}", color_to_string(pixel_color, samples_per_pixel));
 }
@@ -0,0 +1,107 @@
+use crate::aabb::Aabb;
+use crate::cylinder::radial;
+use crate::hittable::{HitRecord, Hittable};
+use crate::material::Material;
+use crate::polynomial::solve_quadratic;
+use crate::ray::Ray;
+use crate::rtweekend::PI;
+use crate::vec3::*;
+
+use std::sync::Arc;
+
+// cone with its circular base on `base` and its apex `height` above it
+// along +y, wrap it in an `Instance` to orient it differently
+pub struct Cone {
+    pub base: Point3,
+    pub radius: f64,
+    pub height: f64,
+    pub capped: bool,
+    pub material: Arc<dyn Material + Sync + Send>,
+}
+
+// constructor and setter functions
+impl Cone {
+    pub fn new(
+        base: Point3,
+        radius: f64,
+        height: f64,
+        capped: bool,
+        material: Arc<dyn Material + Sync + Send>,
+    ) -> Cone {
+        Cone {
+            base,
+            radius,
+            height,
+            capped,
+            material,
+        }
+    }
+}
+
+impl Hittable for Cone {
+    fn hit(&self, r: &Ray, t_min: f64, t_max: f64) -> Option<HitRecord> {
+        let o: Vec3 = r.origin() - self.base;
+        let d: Vec3 = r.direction();
+        // (t, local hit point, outward normal, u, v)
+        let mut closest: Option<(f64, Vec3, Vec3, f64, f64)> = None;
+        let mut consider = |t: f64, p: Vec3, n: Vec3, u: f64, v: f64| {
+            if t_min <= t && t <= t_max && closest.is_none_or(|c| t < c.0) {
+                closest = Some((t, p, n, u, v));
+            }
+        };
+
+        // side: x^2 + z^2 = (k (height - y))^2 for 0 <= y <= height
+        let k = self.radius / self.height;
+        let k2 = k * k;
+        let h = self.height - o.y();
+        let a = d.x() * d.x() + d.z() * d.z() - k2 * d.y() * d.y();
+        let b = 2.0 * (o.x() * d.x() + o.z() * d.z() + k2 * h * d.y());
+        let c = o.x() * o.x() + o.z() * o.z() - k2 * h * h;
+        for t in solve_quadratic(a, b, c) {
+            let p = o + t * d;
+            if 0.0 <= p.y() && p.y() <= self.height {
+                let n = unit_vector(Vec3::new(p.x(), k2 * (self.height - p.y()), p.z()));
+                let u = (p.z().atan2(p.x()) + PI) / (2.0 * PI);
+                consider(t, p, n, u, p.y() / self.height);
+            }
+        }
+
+        if self.capped && d.y() != 0.0 {
+            let t = -o.y() / d.y();
+            let p = o + t * d;
+            let dist = (p.x() * p.x() + p.z() * p.z()).sqrt();
+            if dist <= self.radius {
+                let u = (p.z().atan2(p.x()) + PI) / (2.0 * PI);
+                consider(t, p, Vec3::new(0.0, -1.0, 0.0), u, dist / self.radius);
+            }
+        }
+
+        let (t, p, outward_normal, u, v) = closest?;
+        let mut hr = HitRecord {
+            t,
+            p: p + self.base,
+            normal: Vec3::new(0.0, 0.0, 0.0),
+            u,
+            v,
+            // around the axis, then up the slant to the apex or out across
+            // the base
+            dpdu: 2.0 * PI * Vec3::new(-p.z(), 0.0, p.x()),
+            dpdv: if outward_normal.y() == -1.0 {
+                radial(p, self.radius)
+            } else {
+                Vec3::new(0.0, self.height, 0.0) - radial(p, self.radius)
+            },
+            front_face: false,
+            material: Arc::clone(&self.material),
+        };
+        hr.set_face_normal(r, outward_normal);
+        Some(hr)
+    }
+
+    fn bounding_box(&self) -> Option<Aabb> {
+        Some(Aabb::new(
+            self.base - Vec3::new(self.radius, 0.0, self.radius),
+            self.base + Vec3::new(self.radius, self.height, self.radius),
+        ))
+    }
+}
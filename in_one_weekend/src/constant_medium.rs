@@ -0,0 +1,80 @@
+use crate::aabb::Aabb;
+use crate::hittable::{HitRecord, Hittable};
+use crate::material::{Isotropic, Material};
+use crate::ray::Ray;
+use crate::rtweekend::{random_f64, INFINITY};
+use crate::vec3::*;
+
+use std::sync::Arc;
+
+// homogeneous participating medium filling a closed boundary, e.g. fog or smoke
+pub struct ConstantMedium {
+    pub boundary: Arc<dyn Hittable + Sync + Send>,
+    pub neg_inv_density: f64,
+    pub phase_function: Arc<dyn Material + Sync + Send>,
+}
+
+// constructor functions
+impl ConstantMedium {
+    pub fn new(
+        boundary: Arc<dyn Hittable + Sync + Send>,
+        density: f64,
+        phase_function: Arc<dyn Material + Sync + Send>,
+    ) -> ConstantMedium {
+        ConstantMedium {
+            boundary,
+            neg_inv_density: -1.0 / density,
+            phase_function,
+        }
+    }
+
+    // medium with an isotropic phase function of the given albedo
+    pub fn with_color(
+        boundary: Arc<dyn Hittable + Sync + Send>,
+        density: f64,
+        albedo: Color,
+    ) -> ConstantMedium {
+        ConstantMedium::new(boundary, density, Arc::new(Isotropic::new(albedo)))
+    }
+}
+
+impl Hittable for ConstantMedium {
+    fn hit(&self, r: &Ray, t_min: f64, t_max: f64) -> Option<HitRecord> {
+        // find where the ray enters and exits the boundary, even if the
+        // origin is already inside it
+        let rec1 = self.boundary.hit(r, -INFINITY, INFINITY)?;
+        let rec2 = self.boundary.hit(r, rec1.t + 0.0001, INFINITY)?;
+
+        let t_enter = rec1.t.max(t_min).max(0.0);
+        let t_exit = rec2.t.min(t_max);
+        if t_enter >= t_exit {
+            return None;
+        }
+
+        // sample a free-flight distance from the exponential distribution
+        let ray_length = r.direction().length();
+        let distance_inside_boundary = (t_exit - t_enter) * ray_length;
+        let hit_distance = self.neg_inv_density * random_f64().ln();
+        if hit_distance > distance_inside_boundary {
+            return None;
+        }
+
+        let t = t_enter + hit_distance / ray_length;
+        Some(HitRecord {
+            t,
+            p: r.at(t),
+            // arbitrary, the phase function does not use them
+            normal: Vec3::new(1.0, 0.0, 0.0),
+            u: 0.0,
+            v: 0.0,
+            dpdu: Vec3::new(0.0, 0.0, 0.0),
+            dpdv: Vec3::new(0.0, 0.0, 0.0),
+            front_face: true,
+            material: Arc::clone(&self.phase_function),
+        })
+    }
+
+    fn bounding_box(&self) -> Option<Aabb> {
+        self.boundary.bounding_box()
+    }
+}
@@ -0,0 +1,154 @@
+use crate::aabb::{surrounding_box, Aabb};
+use crate::hittable::{HitRecord, Hittable};
+use crate::ray::Ray;
+use crate::rtweekend::INFINITY;
+use crate::vec3::Point3;
+
+use std::sync::Arc;
+
+// step past a crossing before looking for the next one
+const CROSSING_EPSILON: f64 = 1e-7;
+// guards against objects that keep reporting hits at the same t
+const MAX_CROSSINGS: usize = 64;
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum CsgOp {
+    Union,
+    Intersection,
+    // left with right carved out of it
+    Difference,
+}
+
+impl CsgOp {
+    fn inside(&self, in_left: bool, in_right: bool) -> bool {
+        match self {
+            CsgOp::Union => in_left || in_right,
+            CsgOp::Intersection => in_left && in_right,
+            CsgOp::Difference => in_left && !in_right,
+        }
+    }
+}
+
+// boolean combination of two closed hittables
+pub struct Csg {
+    pub left: Arc<dyn Hittable + Sync + Send>,
+    pub right: Arc<dyn Hittable + Sync + Send>,
+    pub op: CsgOp,
+}
+
+// constructor functions
+impl Csg {
+    pub fn new(
+        left: Arc<dyn Hittable + Sync + Send>,
+        right: Arc<dyn Hittable + Sync + Send>,
+        op: CsgOp,
+    ) -> Csg {
+        Csg { left, right, op }
+    }
+
+    pub fn union(
+        left: Arc<dyn Hittable + Sync + Send>,
+        right: Arc<dyn Hittable + Sync + Send>,
+    ) -> Csg {
+        Csg::new(left, right, CsgOp::Union)
+    }
+
+    pub fn intersection(
+        left: Arc<dyn Hittable + Sync + Send>,
+        right: Arc<dyn Hittable + Sync + Send>,
+    ) -> Csg {
+        Csg::new(left, right, CsgOp::Intersection)
+    }
+
+    pub fn difference(
+        left: Arc<dyn Hittable + Sync + Send>,
+        right: Arc<dyn Hittable + Sync + Send>,
+    ) -> Csg {
+        Csg::new(left, right, CsgOp::Difference)
+    }
+}
+
+// every surface crossing of a closed object along the whole ray, in order
+fn crossings(object: &(dyn Hittable + Sync + Send), r: &Ray) -> Vec<HitRecord> {
+    let mut out: Vec<HitRecord> = vec![];
+    let mut t = -INFINITY;
+    while out.len() < MAX_CROSSINGS {
+        match object.hit(r, t, INFINITY) {
+            Some(rec) => {
+                t = rec.t + CROSSING_EPSILON;
+                out.push(rec);
+            }
+            None => break,
+        }
+    }
+    out
+}
+
+impl Hittable for Csg {
+    fn hit(&self, r: &Ray, t_min: f64, t_max: f64) -> Option<HitRecord> {
+        let left = crossings(self.left.as_ref(), r);
+        let right = crossings(self.right.as_ref(), r);
+
+        // a ray that first exits an object started inside it
+        let mut in_left = left.first().is_some_and(|rec| !rec.front_face);
+        let mut in_right = right.first().is_some_and(|rec| !rec.front_face);
+        let mut inside = self.op.inside(in_left, in_right);
+
+        // walk both interval lists in order until the combined
+        // inside/outside state flips within [t_min, t_max]
+        let (mut i, mut j) = (0, 0);
+        while i < left.len() || j < right.len() {
+            let take_left = j >= right.len() || (i < left.len() && left[i].t <= right[j].t);
+            let rec = if take_left {
+                in_left = left[i].front_face;
+                i += 1;
+                &left[i - 1]
+            } else {
+                in_right = right[j].front_face;
+                j += 1;
+                &right[j - 1]
+            };
+            if rec.t > t_max {
+                return None;
+            }
+
+            let now_inside = self.op.inside(in_left, in_right);
+            if now_inside != inside {
+                inside = now_inside;
+                if rec.t >= t_min {
+                    // the normal already faces the ray, only which side of
+                    // the combined solid we are on can change
+                    let mut out = rec.clone();
+                    out.front_face = now_inside;
+                    return Some(out);
+                }
+            }
+        }
+        None
+    }
+
+    fn bounding_box(&self) -> Option<Aabb> {
+        match self.op {
+            CsgOp::Union => Some(surrounding_box(
+                self.left.bounding_box()?,
+                self.right.bounding_box()?,
+            )),
+            CsgOp::Intersection => match (self.left.bounding_box(), self.right.bounding_box()) {
+                (Some(a), Some(b)) => Some(Aabb::new(
+                    Point3::new(
+                        a.min().x().max(b.min().x()),
+                        a.min().y().max(b.min().y()),
+                        a.min().z().max(b.min().z()),
+                    ),
+                    Point3::new(
+                        a.max().x().min(b.max().x()),
+                        a.max().y().min(b.max().y()),
+                        a.max().z().min(b.max().z()),
+                    ),
+                )),
+                (a, b) => a.or(b),
+            },
+            CsgOp::Difference => self.left.bounding_box(),
+        }
+    }
+}
@@ -0,0 +1,134 @@
+use crate::perlin::Perlin;
+use crate::vec3::*;
+
+use std::fs;
+use std::io::{self, Error, ErrorKind};
+use std::path::Path;
+
+// voxel grid of densities covering the unit cube [0, 1]^3,
+// stored with x varying fastest
+pub struct DensityGrid {
+    pub nx: usize,
+    pub ny: usize,
+    pub nz: usize,
+    pub data: Vec<f64>,
+}
+
+// constructor functions
+impl DensityGrid {
+    pub fn new(nx: usize, ny: usize, nz: usize, data: Vec<f64>) -> DensityGrid {
+        assert_eq!(
+            data.len(),
+            nx * ny * nz,
+            "density grid data does not match its dimensions"
+        );
+        DensityGrid { nx, ny, nz, data }
+    }
+
+    // loads a `.vol` file: an ascii header line "nx ny nz" followed by
+    // nx * ny * nz little endian f32 densities
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<DensityGrid> {
+        let bytes = fs::read(path)?;
+        DensityGrid::from_vol_bytes(&bytes)
+    }
+
+    pub fn from_vol_bytes(bytes: &[u8]) -> io::Result<DensityGrid> {
+        let invalid = |msg: &str| Error::new(ErrorKind::InvalidData, msg.to_string());
+
+        let header_end = bytes
+            .iter()
+            .position(|&b| b == b'\n')
+            .ok_or_else(|| invalid("missing .vol header"))?;
+        let header = std::str::from_utf8(&bytes[..header_end])
+            .map_err(|_| invalid("non utf-8 .vol header"))?;
+        let dims: Vec<usize> = header
+            .split_whitespace()
+            .map(|d| d.parse().map_err(|_| invalid("bad .vol dimension")))
+            .collect::<io::Result<Vec<usize>>>()?;
+        if dims.len() != 3 {
+            return Err(invalid(".vol header must have three dimensions"));
+        }
+
+        let body = &bytes[header_end + 1..];
+        if body.len() != dims[0] * dims[1] * dims[2] * 4 {
+            return Err(invalid(".vol body size does not match its header"));
+        }
+        let data: Vec<f64> = body
+            .chunks_exact(4)
+            .map(|c| f32::from_le_bytes([c[0], c[1], c[2], c[3]]) as f64)
+            .collect();
+        Ok(DensityGrid::new(dims[0], dims[1], dims[2], data))
+    }
+
+    // billowy cloud from perlin turbulence, fading out towards the
+    // edge of the cube so it has no hard boundary
+    pub fn from_perlin(resolution: usize, frequency: f64, threshold: f64) -> DensityGrid {
+        let noise = Perlin::new();
+        let center = Point3::new(0.5, 0.5, 0.5);
+        let n = resolution;
+        let mut data = Vec::with_capacity(n * n * n);
+        for k in 0..n {
+            for j in 0..n {
+                for i in 0..n {
+                    let p = Point3::new(
+                        (i as f64 + 0.5) / n as f64,
+                        (j as f64 + 0.5) / n as f64,
+                        (k as f64 + 0.5) / n as f64,
+                    );
+                    let falloff = (1.0 - 2.0 * (p - center).length()).max(0.0);
+                    let t = noise.turb(&(frequency * p), 7);
+                    let density = ((t - threshold) / (1.0 - threshold)).max(0.0);
+                    data.push(density * falloff);
+                }
+            }
+        }
+        DensityGrid::new(n, n, n, data)
+    }
+}
+
+// member functions
+impl DensityGrid {
+    pub fn voxel(&self, i: usize, j: usize, k: usize) -> f64 {
+        self.data[(k * self.ny + j) * self.nx + i]
+    }
+
+    // upper bound on the density anywhere in the grid
+    pub fn max_density(&self) -> f64 {
+        self.data.iter().cloned().fold(0.0, f64::max)
+    }
+
+    // trilinearly interpolated density at a point in the unit cube,
+    // voxel values are taken to lie at the cell centers
+    pub fn density(&self, p: Point3) -> f64 {
+        let dims = [self.nx, self.ny, self.nz];
+        let mut lo = [0usize; 3];
+        let mut hi = [0usize; 3];
+        let mut frac = [0.0; 3];
+        for a in 0..3 {
+            let x = (p[a] * dims[a] as f64 - 0.5).clamp(0.0, (dims[a] - 1) as f64);
+            lo[a] = x.floor() as usize;
+            hi[a] = (lo[a] + 1).min(dims[a] - 1);
+            frac[a] = x - lo[a] as f64;
+        }
+
+        let mut accum = 0.0;
+        for corner in 0..8 {
+            let pick = |a: usize| corner >> a & 1 == 1;
+            let mut weight = 1.0;
+            let mut index = [0usize; 3];
+            for a in 0..3 {
+                if pick(a) {
+                    weight *= frac[a];
+                    index[a] = hi[a];
+                } else {
+                    weight *= 1.0 - frac[a];
+                    index[a] = lo[a];
+                }
+            }
+            if weight > 0.0 {
+                accum += weight * self.voxel(index[0], index[1], index[2]);
+            }
+        }
+        accum
+    }
+}
@@ -0,0 +1,87 @@
+use crate::aabb::Aabb;
+use crate::hittable::{HitRecord, Hittable};
+use crate::material::Material;
+use crate::onb::Onb;
+use crate::ray::Ray;
+use crate::rtweekend::PI;
+use crate::vec3::*;
+
+use std::sync::Arc;
+
+// flat circular disk facing along `normal`
+pub struct Disk {
+    pub center: Point3,
+    pub normal: Vec3,
+    pub radius: f64,
+    pub material: Arc<dyn Material + Sync + Send>,
+    basis: Onb,
+}
+
+// constructor and setter functions
+impl Disk {
+    pub fn new(
+        center: Point3,
+        normal: Vec3,
+        radius: f64,
+        material: Arc<dyn Material + Sync + Send>,
+    ) -> Disk {
+        let basis = Onb::build_from_w(normal);
+        Disk {
+            center,
+            normal: basis.w(),
+            radius,
+            material,
+            basis,
+        }
+    }
+}
+
+impl Hittable for Disk {
+    fn hit(&self, r: &Ray, t_min: f64, t_max: f64) -> Option<HitRecord> {
+        let denom = dot(&self.normal, &r.direction());
+        if denom.abs() < 1e-12 {
+            return None;
+        }
+        let t = dot(&(self.center - r.origin()), &self.normal) / denom;
+        if t < t_min || t_max < t {
+            return None;
+        }
+        let p = r.at(t);
+        let local = self.basis.to_local(p - self.center);
+        let dist = (local.x() * local.x() + local.y() * local.y()).sqrt();
+        if dist > self.radius {
+            return None;
+        }
+
+        // u: angle around the center, v: distance from the center
+        let phi = local.y().atan2(local.x());
+        let mut hr = HitRecord {
+            t,
+            p,
+            normal: Vec3::new(0.0, 0.0, 0.0),
+            u: (phi + PI) / (2.0 * PI),
+            v: dist / self.radius,
+            dpdu: Vec3::new(0.0, 0.0, 0.0),
+            dpdv: Vec3::new(0.0, 0.0, 0.0),
+            front_face: false,
+            material: Arc::clone(&self.material),
+        };
+        hr.set_face_normal(r, self.normal);
+        hr.dpdu = 2.0 * PI * self.basis.local(-local.y(), local.x(), 0.0);
+        if dist > 0.0 {
+            hr.dpdv = self.radius / dist * self.basis.local(local.x(), local.y(), 0.0);
+        }
+        Some(hr)
+    }
+
+    fn bounding_box(&self) -> Option<Aabb> {
+        // extent of the disk along each axis, padded so it is never flat
+        let n = self.normal;
+        let e = Vec3::new(
+            self.radius * (1.0 - n.x() * n.x()).max(0.0).sqrt() + 1e-4,
+            self.radius * (1.0 - n.y() * n.y()).max(0.0).sqrt() + 1e-4,
+            self.radius * (1.0 - n.z() * n.z()).max(0.0).sqrt() + 1e-4,
+        );
+        Some(Aabb::new(self.center - e, self.center + e))
+    }
+}
@@ -0,0 +1,48 @@
+use crate::ray::Ray;
+use crate::rtweekend::random_f64;
+use crate::scenes::{ray_color, ray_color_spectral, ImageSettings, SceneSettings};
+use crate::vec3::*;
+
+// film coordinates of the point (x, y) on the image, in pixels. pixel
+// (i, j) covers [i, i + 1) x [j, j + 1), rows counted from the bottom
+pub fn film_coordinates(image_settings: &ImageSettings, x: f64, y: f64) -> (f64, f64) {
+    let u: f64 = x / ((image_settings.image_width - 1) as f64);
+    let v: f64 = y / ((image_settings.image_height - 1) as f64);
+    (u, v)
+}
+
+// sum of all samples taken through pixel (i, j), counting rows from the
+// bottom of the image
+pub fn sample_pixel(scene_settings: &SceneSettings, i: i32, j: i32) -> Color {
+    let SceneSettings {
+        world,
+        cam,
+        image_settings,
+    } = scene_settings;
+    let ImageSettings {
+        samples_per_pixel,
+        max_depth,
+        spectral,
+        ..
+    } = *image_settings;
+    let color_fn = if spectral {
+        ray_color_spectral
+    } else {
+        ray_color
+    };
+
+    let mut pixel_color: Color = Color::new(0.0, 0.0, 0.0);
+    for _ in 0..samples_per_pixel {
+        let (u, v) = film_coordinates(
+            image_settings,
+            (i as f64) + random_f64(),
+            (j as f64) + random_f64(),
+        );
+        if !cam.in_frame(u, v) {
+            continue;
+        }
+        let r: Ray = cam.get_ray(u, v);
+        pixel_color += color_fn(&r, world, max_depth);
+    }
+    cam.exposure() * pixel_color
+}
@@ -0,0 +1,149 @@
+use crate::thin_film::ThinFilm;
+use crate::vec3::Color;
+
+// exact fresnel reflectance for unpolarized light at a smooth dielectric
+// boundary. cos_theta_i is measured on the incident side and eta is the
+// relative index of refraction eta_t / eta_i
+pub fn fresnel_dielectric(cos_theta_i: f64, eta: f64) -> f64 {
+    let mut cos_theta_i = cos_theta_i.clamp(-1.0, 1.0);
+    let mut eta = eta;
+    // arriving from the other side of the boundary
+    if cos_theta_i < 0.0 {
+        eta = 1.0 / eta;
+        cos_theta_i = -cos_theta_i;
+    }
+
+    let sin2_theta_i = 1.0 - cos_theta_i * cos_theta_i;
+    let sin2_theta_t = sin2_theta_i / (eta * eta);
+    if sin2_theta_t >= 1.0 {
+        // total internal reflection
+        return 1.0;
+    }
+    let cos_theta_t = (1.0 - sin2_theta_t).sqrt();
+
+    let r_parl = (eta * cos_theta_i - cos_theta_t) / (eta * cos_theta_i + cos_theta_t);
+    let r_perp = (cos_theta_i - eta * cos_theta_t) / (cos_theta_i + eta * cos_theta_t);
+    (r_parl * r_parl + r_perp * r_perp) / 2.0
+}
+
+// schlick's approximation, for tinted conductors given their reflectance
+// at normal incidence
+pub fn fresnel_schlick(cos_theta: f64, f0: f64) -> f64 {
+    f0 + (1.0 - f0) * (1.0 - cos_theta.clamp(0.0, 1.0)).powi(5)
+}
+
+// exact fresnel reflectance for unpolarized light at a conductor with
+// complex index of refraction eta + ik, relative to the incident medium
+pub fn fresnel_complex(cos_theta_i: f64, eta: f64, k: f64) -> f64 {
+    let cos_theta_i = cos_theta_i.clamp(0.0, 1.0);
+    let cos2 = cos_theta_i * cos_theta_i;
+    let sin2 = 1.0 - cos2;
+    let eta2 = eta * eta;
+    let k2 = k * k;
+
+    let t0 = eta2 - k2 - sin2;
+    let a2_plus_b2 = (t0 * t0 + 4.0 * eta2 * k2).sqrt();
+    let a = (0.5 * (a2_plus_b2 + t0)).max(0.0).sqrt();
+
+    let t1 = a2_plus_b2 + cos2;
+    let t2 = 2.0 * a * cos_theta_i;
+    let r_perp = (t1 - t2) / (t1 + t2);
+
+    let t3 = cos2 * a2_plus_b2 + sin2 * sin2;
+    let t4 = t2 * sin2;
+    let r_parl = r_perp * (t3 - t4) / (t3 + t4);
+
+    (r_perp + r_parl) / 2.0
+}
+
+// per channel complex index of refraction of a conductor
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct ComplexIor {
+    pub eta: Color,
+    pub k: Color,
+}
+
+// constructor functions, presets are sampled at roughly 650, 550 and 450 nm
+impl ComplexIor {
+    pub fn new(eta: Color, k: Color) -> ComplexIor {
+        ComplexIor { eta, k }
+    }
+
+    pub fn gold() -> ComplexIor {
+        ComplexIor::new(
+            Color::new(0.143, 0.374, 1.442),
+            Color::new(3.983, 2.385, 1.603),
+        )
+    }
+
+    pub fn silver() -> ComplexIor {
+        ComplexIor::new(
+            Color::new(0.155, 0.117, 0.138),
+            Color::new(4.828, 3.122, 2.147),
+        )
+    }
+
+    pub fn copper() -> ComplexIor {
+        ComplexIor::new(
+            Color::new(0.200, 0.924, 1.102),
+            Color::new(3.912, 2.452, 2.142),
+        )
+    }
+
+    pub fn aluminum() -> ComplexIor {
+        ComplexIor::new(
+            Color::new(1.657, 0.880, 0.521),
+            Color::new(9.224, 6.270, 4.837),
+        )
+    }
+
+    pub fn iron() -> ComplexIor {
+        ComplexIor::new(
+            Color::new(2.911, 2.950, 2.584),
+            Color::new(3.089, 2.932, 2.767),
+        )
+    }
+}
+
+// member functions
+impl ComplexIor {
+    pub fn reflectance(&self, cos_theta_i: f64) -> Color {
+        Color::new(
+            fresnel_complex(cos_theta_i, self.eta.x(), self.k.x()),
+            fresnel_complex(cos_theta_i, self.eta.y(), self.k.y()),
+            fresnel_complex(cos_theta_i, self.eta.z(), self.k.z()),
+        )
+    }
+}
+
+// how a conductor's reflectance varies with angle
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum ConductorFresnel {
+    // artist friendly tint at normal incidence
+    Schlick(Color),
+    // measured optical constants
+    Complex(ComplexIor),
+    // measured optical constants under an iridescent film
+    ThinFilm(ComplexIor, ThinFilm),
+}
+
+impl ConductorFresnel {
+    pub fn reflectance(&self, cos_theta_i: f64) -> Color {
+        self.reflectance_at(cos_theta_i, None)
+    }
+
+    // as reflectance, but films evaluate at the ray's wavelength if any
+    pub fn reflectance_at(&self, cos_theta_i: f64, wavelength: Option<f64>) -> Color {
+        match self {
+            ConductorFresnel::Schlick(f0) => Color::new(
+                fresnel_schlick(cos_theta_i, f0.x()),
+                fresnel_schlick(cos_theta_i, f0.y()),
+                fresnel_schlick(cos_theta_i, f0.z()),
+            ),
+            ConductorFresnel::Complex(ior) => ior.reflectance(cos_theta_i),
+            ConductorFresnel::ThinFilm(ior, film) => {
+                film.reflectance_conductor(cos_theta_i, ior, wavelength)
+            }
+        }
+    }
+}
@@ -0,0 +1,138 @@
+use crate::aabb::Aabb;
+use crate::density_grid::DensityGrid;
+use crate::hittable::{HitRecord, Hittable};
+use crate::material::{Isotropic, Material};
+use crate::ray::Ray;
+use crate::rtweekend::random_f64;
+use crate::vec3::*;
+
+use std::sync::Arc;
+
+// heterogeneous participating medium, e.g. clouds, with densities read
+// from a voxel grid stretched over an axis aligned box
+pub struct GridMedium {
+    pub grid: Arc<DensityGrid>,
+    pub bounds: Aabb,
+    pub density_scale: f64,
+    pub phase_function: Arc<dyn Material + Sync + Send>,
+    majorant: f64,
+}
+
+// constructor functions
+impl GridMedium {
+    pub fn new(
+        grid: Arc<DensityGrid>,
+        bounds: Aabb,
+        density_scale: f64,
+        phase_function: Arc<dyn Material + Sync + Send>,
+    ) -> GridMedium {
+        let majorant = grid.max_density() * density_scale;
+        GridMedium {
+            grid,
+            bounds,
+            density_scale,
+            phase_function,
+            majorant,
+        }
+    }
+
+    pub fn with_color(
+        grid: Arc<DensityGrid>,
+        bounds: Aabb,
+        density_scale: f64,
+        albedo: Color,
+    ) -> GridMedium {
+        GridMedium::new(
+            grid,
+            bounds,
+            density_scale,
+            Arc::new(Isotropic::new(albedo)),
+        )
+    }
+}
+
+// member functions
+impl GridMedium {
+    pub fn majorant(&self) -> f64 {
+        self.majorant
+    }
+
+    // density at a world space point
+    pub fn density(&self, p: Point3) -> f64 {
+        let extent = self.bounds.max() - self.bounds.min();
+        let local = p - self.bounds.min();
+        let uvw = Point3::new(
+            local.x() / extent.x(),
+            local.y() / extent.y(),
+            local.z() / extent.z(),
+        );
+        self.grid.density(uvw) * self.density_scale
+    }
+
+    // step to the next tentative collision against the majorant,
+    // returns the new t in ray parameter units
+    fn next_tentative(&self, t: f64, ray_length: f64) -> f64 {
+        t - (1.0 - random_f64()).ln() / (self.majorant * ray_length)
+    }
+}
+
+impl Hittable for GridMedium {
+    // delta tracking: tentative collisions are sampled against the
+    // homogeneous majorant and accepted with probability density / majorant
+    fn hit(&self, r: &Ray, t_min: f64, t_max: f64) -> Option<HitRecord> {
+        let (t0, t1) = self.bounds.hit_interval(r, t_min, t_max)?;
+        if self.majorant <= 0.0 {
+            return None;
+        }
+
+        let ray_length = r.direction().length();
+        let mut t = t0;
+        loop {
+            t = self.next_tentative(t, ray_length);
+            if t >= t1 {
+                return None;
+            }
+            let p = r.at(t);
+            if random_f64() * self.majorant < self.density(p) {
+                return Some(HitRecord {
+                    t,
+                    p,
+                    // arbitrary, the phase function does not use them
+                    normal: Vec3::new(1.0, 0.0, 0.0),
+                    u: 0.0,
+                    v: 0.0,
+                    dpdu: Vec3::new(0.0, 0.0, 0.0),
+                    dpdv: Vec3::new(0.0, 0.0, 0.0),
+                    front_face: true,
+                    material: Arc::clone(&self.phase_function),
+                });
+            }
+        }
+    }
+
+    fn bounding_box(&self) -> Option<Aabb> {
+        Some(self.bounds)
+    }
+
+    // ratio tracking: every tentative collision passes on the fraction
+    // of light the null collision lets through
+    fn transmittance(&self, r: &Ray, t_min: f64, t_max: f64) -> f64 {
+        let Some((t0, t1)) = self.bounds.hit_interval(r, t_min, t_max) else {
+            return 1.0;
+        };
+        if self.majorant <= 0.0 {
+            return 1.0;
+        }
+
+        let ray_length = r.direction().length();
+        let mut transmittance = 1.0;
+        let mut t = t0;
+        loop {
+            t = self.next_tentative(t, ray_length);
+            if t >= t1 {
+                return transmittance;
+            }
+            transmittance *= 1.0 - self.density(r.at(t)) / self.majorant;
+        }
+    }
+}
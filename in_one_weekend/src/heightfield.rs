@@ -0,0 +1,364 @@
+use crate::aabb::Aabb;
+use crate::hittable::{HitRecord, Hittable};
+use crate::image::Image;
+use crate::material::Material;
+use crate::ray::Ray;
+use crate::vec3::*;
+
+use std::io::{self, Error, ErrorKind};
+use std::sync::Arc;
+
+// terrain from a regular grid of height samples in [0, 1], spanning
+// `size.x` by `size.z` from `corner` and rising up to `size.y` above it.
+// each grid cell is split into two triangles, and rays are traced
+// through a min-max mip hierarchy over the cells
+pub struct Heightfield {
+    pub nx: usize,
+    pub nz: usize,
+    pub heights: Vec<f64>,
+    pub corner: Point3,
+    pub size: Vec3,
+    pub material: Arc<dyn Material + Sync + Send>,
+    normals: Vec<Vec3>,
+    // levels[0] holds the (min, max) height of every cell, each
+    // following level merges 2x2 blocks of the one below it
+    levels: Vec<MipLevel>,
+}
+
+// (t, interpolated normal, upward geometric normal) of a hit in a cell
+type CellHit = (f64, Vec3, Vec3);
+
+struct MipLevel {
+    width: usize,
+    depth: usize,
+    bounds: Vec<(f64, f64)>,
+}
+
+impl MipLevel {
+    fn get(&self, i: usize, j: usize) -> (f64, f64) {
+        self.bounds[j * self.width + i]
+    }
+}
+
+// constructor functions
+impl Heightfield {
+    pub fn new(
+        nx: usize,
+        nz: usize,
+        heights: Vec<f64>,
+        corner: Point3,
+        size: Vec3,
+        material: Arc<dyn Material + Sync + Send>,
+    ) -> Heightfield {
+        assert!(nx >= 2 && nz >= 2, "heightfield needs at least 2x2 samples");
+        assert_eq!(
+            heights.len(),
+            nx * nz,
+            "heightfield data does not match its dimensions"
+        );
+        let mut hf = Heightfield {
+            nx,
+            nz,
+            heights,
+            corner,
+            size,
+            material,
+            normals: vec![],
+            levels: vec![],
+        };
+        hf.normals = hf.compute_normals();
+        hf.levels = hf.build_levels();
+        hf
+    }
+
+    // heights from the luminance of an image, the top row of the image
+    // is the far (+z) edge of the terrain
+    pub fn from_image(
+        image: &Image,
+        corner: Point3,
+        size: Vec3,
+        material: Arc<dyn Material + Sync + Send>,
+    ) -> io::Result<Heightfield> {
+        let (nx, nz) = (image.width, image.height);
+        if nx < 2 || nz < 2 {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                "heightfield image needs at least 2x2 pixels",
+            ));
+        }
+        let mut heights = Vec::with_capacity(nx * nz);
+        for j in 0..nz {
+            for i in 0..nx {
+                heights.push(image.luminance(i, nz - 1 - j));
+            }
+        }
+        Ok(Heightfield::new(nx, nz, heights, corner, size, material))
+    }
+
+    // heights sampled from a function of the grid coordinates in [0, 1]^2,
+    // e.g. perlin turbulence
+    pub fn from_fn<F: Fn(f64, f64) -> f64>(
+        nx: usize,
+        nz: usize,
+        f: F,
+        corner: Point3,
+        size: Vec3,
+        material: Arc<dyn Material + Sync + Send>,
+    ) -> Heightfield {
+        let mut heights = Vec::with_capacity(nx * nz);
+        for j in 0..nz {
+            for i in 0..nx {
+                let s = i as f64 / (nx - 1) as f64;
+                let t = j as f64 / (nz - 1) as f64;
+                heights.push(f(s, t));
+            }
+        }
+        Heightfield::new(nx, nz, heights, corner, size, material)
+    }
+
+    // per vertex normals from central differences of the grid
+    fn compute_normals(&self) -> Vec<Vec3> {
+        let dx = self.size.x() / (self.nx - 1) as f64;
+        let dz = self.size.z() / (self.nz - 1) as f64;
+        let mut normals = Vec::with_capacity(self.nx * self.nz);
+        for j in 0..self.nz {
+            for i in 0..self.nx {
+                let (i0, i1) = (i.saturating_sub(1), (i + 1).min(self.nx - 1));
+                let (j0, j1) = (j.saturating_sub(1), (j + 1).min(self.nz - 1));
+                let slope_x = (self.height(i1, j) - self.height(i0, j)) * self.size.y()
+                    / ((i1 - i0) as f64 * dx);
+                let slope_z = (self.height(i, j1) - self.height(i, j0)) * self.size.y()
+                    / ((j1 - j0) as f64 * dz);
+                normals.push(unit_vector(Vec3::new(-slope_x, 1.0, -slope_z)));
+            }
+        }
+        normals
+    }
+
+    fn build_levels(&self) -> Vec<MipLevel> {
+        let (cells_x, cells_z) = (self.nx - 1, self.nz - 1);
+        let mut bounds = Vec::with_capacity(cells_x * cells_z);
+        for j in 0..cells_z {
+            for i in 0..cells_x {
+                let h = [
+                    self.height(i, j),
+                    self.height(i + 1, j),
+                    self.height(i, j + 1),
+                    self.height(i + 1, j + 1),
+                ];
+                let lo = h.iter().cloned().fold(f64::INFINITY, f64::min);
+                let hi = h.iter().cloned().fold(-f64::INFINITY, f64::max);
+                bounds.push((lo, hi));
+            }
+        }
+        let mut levels = vec![MipLevel {
+            width: cells_x,
+            depth: cells_z,
+            bounds,
+        }];
+
+        while levels.last().is_some_and(|l| l.width > 1 || l.depth > 1) {
+            let below = levels.last().unwrap();
+            let width = below.width.div_ceil(2);
+            let depth = below.depth.div_ceil(2);
+            let mut bounds = Vec::with_capacity(width * depth);
+            for j in 0..depth {
+                for i in 0..width {
+                    let mut merged = (f64::INFINITY, -f64::INFINITY);
+                    for cj in (2 * j)..(2 * j + 2).min(below.depth) {
+                        for ci in (2 * i)..(2 * i + 2).min(below.width) {
+                            let (lo, hi) = below.get(ci, cj);
+                            merged = (merged.0.min(lo), merged.1.max(hi));
+                        }
+                    }
+                    bounds.push(merged);
+                }
+            }
+            levels.push(MipLevel {
+                width,
+                depth,
+                bounds,
+            });
+        }
+        levels
+    }
+}
+
+// member functions
+impl Heightfield {
+    pub fn height(&self, i: usize, j: usize) -> f64 {
+        self.heights[j * self.nx + i]
+    }
+
+    fn vertex(&self, i: usize, j: usize) -> Point3 {
+        self.corner
+            + Vec3::new(
+                self.size.x() * i as f64 / (self.nx - 1) as f64,
+                self.size.y() * self.height(i, j),
+                self.size.z() * j as f64 / (self.nz - 1) as f64,
+            )
+    }
+
+    fn normal(&self, i: usize, j: usize) -> Vec3 {
+        self.normals[j * self.nx + i]
+    }
+
+    // world space box of a node of the given level
+    fn node_box(&self, level: usize, i: usize, j: usize) -> Aabb {
+        let cells = 1 << level;
+        let (lo, hi) = self.levels[level].get(i, j);
+        let i1 = ((i + 1) * cells).min(self.nx - 1);
+        let j1 = ((j + 1) * cells).min(self.nz - 1);
+        let x = |i: usize| self.corner.x() + self.size.x() * i as f64 / (self.nx - 1) as f64;
+        let z = |j: usize| self.corner.z() + self.size.z() * j as f64 / (self.nz - 1) as f64;
+        // pad so flat regions still have a box with volume
+        let pad = 1e-6 * (1.0 + self.size.y().abs());
+        Aabb::new(
+            Point3::new(
+                x(i * cells),
+                self.corner.y() + self.size.y() * lo - pad,
+                z(j * cells),
+            ),
+            Point3::new(x(i1), self.corner.y() + self.size.y() * hi + pad, z(j1)),
+        )
+    }
+
+    // closest hit against the two triangles of a cell
+    fn hit_cell(&self, r: &Ray, i: usize, j: usize, t_min: f64, t_max: f64) -> Option<CellHit> {
+        let corners = [(i, j), (i + 1, j), (i + 1, j + 1), (i, j + 1)];
+        let mut closest: Option<CellHit> = None;
+        for tri in [[0, 1, 2], [0, 2, 3]] {
+            let [a, b, c] = tri.map(|k| corners[k]);
+            let t_limit = closest.map_or(t_max, |c| c.0);
+            let (va, vb, vc) = (
+                self.vertex(a.0, a.1),
+                self.vertex(b.0, b.1),
+                self.vertex(c.0, c.1),
+            );
+            if let Some((t, b1, b2)) = hit_triangle(r, va, vb, vc, t_min, t_limit) {
+                let n = (1.0 - b1 - b2) * self.normal(a.0, a.1)
+                    + b1 * self.normal(b.0, b.1)
+                    + b2 * self.normal(c.0, c.1);
+                // both triangles wind clockwise seen from above
+                let geometric = unit_vector(cross(&(vc - va), &(vb - va)));
+                closest = Some((t, unit_vector(n), geometric));
+            }
+        }
+        closest
+    }
+
+    // visits the children of a node front to back
+    fn traverse(
+        &self,
+        r: &Ray,
+        level: usize,
+        i: usize,
+        j: usize,
+        t_min: f64,
+        t_max: f64,
+    ) -> Option<CellHit> {
+        if level == 0 {
+            return self.hit_cell(r, i, j, t_min, t_max);
+        }
+
+        let below = &self.levels[level - 1];
+        let mut children: Vec<(f64, usize, usize)> = vec![];
+        for cj in (2 * j)..(2 * j + 2).min(below.depth) {
+            for ci in (2 * i)..(2 * i + 2).min(below.width) {
+                if let Some((t0, _)) = self
+                    .node_box(level - 1, ci, cj)
+                    .hit_interval(r, t_min, t_max)
+                {
+                    children.push((t0, ci, cj));
+                }
+            }
+        }
+        children.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        let mut closest: Option<CellHit> = None;
+        for (t0, ci, cj) in children {
+            let t_limit = closest.map_or(t_max, |c| c.0);
+            if t0 > t_limit {
+                break;
+            }
+            if let Some(hit) = self.traverse(r, level - 1, ci, cj, t_min, t_limit) {
+                closest = Some(hit);
+            }
+        }
+        closest
+    }
+}
+
+// moller-trumbore ray/triangle test, returns (t, b1, b2) barycentrics
+fn hit_triangle(
+    r: &Ray,
+    v0: Point3,
+    v1: Point3,
+    v2: Point3,
+    t_min: f64,
+    t_max: f64,
+) -> Option<(f64, f64, f64)> {
+    let e1 = v1 - v0;
+    let e2 = v2 - v0;
+    let pvec = cross(&r.direction(), &e2);
+    let det = dot(&e1, &pvec);
+    if det.abs() < 1e-12 {
+        return None;
+    }
+    let inv_det = 1.0 / det;
+    let tvec = r.origin() - v0;
+    let b1 = dot(&tvec, &pvec) * inv_det;
+    if !(0.0..=1.0).contains(&b1) {
+        return None;
+    }
+    let qvec = cross(&tvec, &e1);
+    let b2 = dot(&r.direction(), &qvec) * inv_det;
+    if b2 < 0.0 || b1 + b2 > 1.0 {
+        return None;
+    }
+    let t = dot(&e2, &qvec) * inv_det;
+    if t < t_min || t_max < t {
+        return None;
+    }
+    Some((t, b1, b2))
+}
+
+impl Hittable for Heightfield {
+    fn hit(&self, r: &Ray, t_min: f64, t_max: f64) -> Option<HitRecord> {
+        let top = self.levels.len() - 1;
+        self.node_box(top, 0, 0).hit_interval(r, t_min, t_max)?;
+        let (t, shading_normal, geometric_normal) = self.traverse(r, top, 0, 0, t_min, t_max)?;
+
+        let p = r.at(t);
+        let mut hr = HitRecord {
+            t,
+            p,
+            normal: Vec3::new(0.0, 0.0, 0.0),
+            u: ((p.x() - self.corner.x()) / self.size.x()).clamp(0.0, 1.0),
+            v: ((p.z() - self.corner.z()) / self.size.z()).clamp(0.0, 1.0),
+            dpdu: Vec3::new(0.0, 0.0, 0.0),
+            dpdv: Vec3::new(0.0, 0.0, 0.0),
+            front_face: false,
+            material: Arc::clone(&self.material),
+        };
+        hr.set_face_normal(r, geometric_normal);
+        // the smooth normal is only used where it still faces the ray
+        let smooth = if hr.front_face {
+            shading_normal
+        } else {
+            -shading_normal
+        };
+        if dot(&r.direction(), &smooth) < 0.0 {
+            hr.normal = smooth;
+        }
+        // u runs along x and v along z, climbing with the triangle
+        let n = geometric_normal;
+        hr.dpdu = Vec3::new(self.size.x(), -self.size.x() * n.x() / n.y(), 0.0);
+        hr.dpdv = Vec3::new(0.0, -self.size.z() * n.z() / n.y(), self.size.z());
+        Some(hr)
+    }
+
+    fn bounding_box(&self) -> Option<Aabb> {
+        Some(self.node_box(self.levels.len() - 1, 0, 0))
+    }
+}
@@ -1,4 +1,6 @@
+use crate::aabb::Aabb;
 use crate::material::Material;
+use crate::onb::Onb;
 use crate::ray::Ray;
 use crate::vec3::*;
 
@@ -9,6 +11,13 @@ pub struct HitRecord {
     pub p: Point3,
     pub normal: Vec3,
     pub t: f64,
+    // surface coordinates for texturing
+    pub u: f64,
+    pub v: f64,
+    // partial derivatives of the hit point with respect to u and v, zero
+    // when a shape has no smooth parameterization
+    pub dpdu: Vec3,
+    pub dpdv: Vec3,
     pub front_face: bool,
     pub material: Arc<dyn Material + Sync + Send>,
 }
@@ -22,10 +31,40 @@ impl HitRecord {
             -outward_normal
         };
     }
+
+    // orthonormal frame around the normal with u along dpdu and v on the
+    // side of dpdv, as tangent space maps expect. without usable
+    // derivatives the tangents are arbitrary
+    pub fn tangent_frame(&self) -> Onb {
+        let w = unit_vector(self.normal);
+        let t = self.dpdu - dot(&self.dpdu, &w) * w;
+        if t.near_zero() {
+            return Onb::build_from_w(w);
+        }
+        let u = unit_vector(t);
+        let mut v = cross(&w, &u);
+        if dot(&v, &self.dpdv) < 0.0 {
+            v = -v;
+        }
+        Onb { axis: [u, v, w] }
+    }
 }
 
 pub trait Hittable {
     fn hit(&self, r: &Ray, t_min: f64, t_max: f64) -> Option<HitRecord>;
+    // None for unbounded objects
+    fn bounding_box(&self) -> Option<Aabb>;
+
+    // estimate of the fraction of light that gets along r between t_min
+    // and t_max, for shadow rays. anything hit blocks the ray, which for
+    // media sampling their own collisions is already an unbiased estimate
+    fn transmittance(&self, r: &Ray, t_min: f64, t_max: f64) -> f64 {
+        if self.hit(r, t_min, t_max).is_some() {
+            0.0
+        } else {
+            1.0
+        }
+    }
 }
 
 // unsafe impl Sync for Box<dyn Hittable> {}
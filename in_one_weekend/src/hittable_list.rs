@@ -1,3 +1,4 @@
+use crate::aabb::{surrounding_box, Aabb};
 use crate::hittable::*;
 use crate::ray::Ray;
 
@@ -19,6 +20,12 @@ impl HittableList {
     }
 }
 
+impl Default for HittableList {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 // modifiers
 impl HittableList {
     #[allow(dead_code)]
@@ -44,4 +51,27 @@ impl Hittable for HittableList {
         }
         temp_rec
     }
+
+    fn bounding_box(&self) -> Option<Aabb> {
+        let mut output_box: Option<Aabb> = None;
+        for object in self.objects.iter() {
+            let b = object.bounding_box()?;
+            output_box = Some(match output_box {
+                Some(ob) => surrounding_box(ob, b),
+                None => b,
+            });
+        }
+        output_box
+    }
+
+    fn transmittance(&self, r: &Ray, t_min: f64, t_max: f64) -> f64 {
+        let mut transmittance: f64 = 1.0;
+        for object in self.objects.iter() {
+            transmittance *= object.transmittance(r, t_min, t_max);
+            if transmittance <= 0.0 {
+                return 0.0;
+            }
+        }
+        transmittance
+    }
 }
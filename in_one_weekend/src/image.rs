@@ -0,0 +1,156 @@
+use crate::color::luminance;
+use crate::vec3::Color;
+
+use std::fs;
+use std::io::{self, Error, ErrorKind};
+use std::path::Path;
+
+// rgb image with channels normalized to [0, 1], row 0 at the top
+pub struct Image {
+    pub width: usize,
+    pub height: usize,
+    pub data: Vec<Color>,
+}
+
+// constructor functions
+impl Image {
+    pub fn new(width: usize, height: usize, data: Vec<Color>) -> Image {
+        assert_eq!(
+            data.len(),
+            width * height,
+            "image data does not match its dimensions"
+        );
+        Image {
+            width,
+            height,
+            data,
+        }
+    }
+
+    // loads a netpbm image: ascii or binary ppm (P3/P6) or pgm (P2/P5),
+    // the same family of formats the renderer writes
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Image> {
+        let bytes = fs::read(path)?;
+        Image::from_netpbm_bytes(&bytes)
+    }
+
+    pub fn from_netpbm_bytes(bytes: &[u8]) -> io::Result<Image> {
+        let invalid = |msg: &str| Error::new(ErrorKind::InvalidData, msg.to_string());
+
+        // read the four header tokens, skipping whitespace and comments
+        let mut pos = 0;
+        let mut header: Vec<String> = vec![];
+        while header.len() < 4 {
+            while pos < bytes.len() && bytes[pos].is_ascii_whitespace() {
+                pos += 1;
+            }
+            if pos < bytes.len() && bytes[pos] == b'#' {
+                while pos < bytes.len() && bytes[pos] != b'\n' {
+                    pos += 1;
+                }
+                continue;
+            }
+            let start = pos;
+            while pos < bytes.len() && !bytes[pos].is_ascii_whitespace() {
+                pos += 1;
+            }
+            if start == pos {
+                return Err(invalid("truncated netpbm header"));
+            }
+            header.push(String::from_utf8_lossy(&bytes[start..pos]).to_string());
+        }
+        // a single whitespace byte separates the header from binary data
+        pos += 1;
+
+        let parse = |s: &str| {
+            s.parse::<usize>()
+                .map_err(|_| invalid("bad netpbm header value"))
+        };
+        let width = parse(&header[1])?;
+        let height = parse(&header[2])?;
+        let max_value = parse(&header[3])?;
+        if max_value == 0 || max_value > 65535 {
+            return Err(invalid("bad netpbm max value"));
+        }
+
+        let (channels, binary) = match header[0].as_str() {
+            "P2" => (1, false),
+            "P3" => (3, false),
+            "P5" => (1, true),
+            "P6" => (3, true),
+            _ => return Err(invalid("unsupported netpbm format")),
+        };
+        let count = width * height * channels;
+
+        let samples: Vec<usize> = if binary {
+            let bytes_per_sample = if max_value < 256 { 1 } else { 2 };
+            let body = bytes.get(pos..).unwrap_or(&[]);
+            if body.len() < count * bytes_per_sample {
+                return Err(invalid("truncated netpbm data"));
+            }
+            body.chunks_exact(bytes_per_sample)
+                .take(count)
+                .map(|c| {
+                    if bytes_per_sample == 1 {
+                        c[0] as usize
+                    } else {
+                        ((c[0] as usize) << 8) | c[1] as usize
+                    }
+                })
+                .collect()
+        } else {
+            let body = String::from_utf8_lossy(bytes.get(pos..).unwrap_or(&[])).to_string();
+            let values = body
+                .split_whitespace()
+                .take(count)
+                .map(parse)
+                .collect::<io::Result<Vec<usize>>>()?;
+            if values.len() < count {
+                return Err(invalid("truncated netpbm data"));
+            }
+            values
+        };
+
+        let scale = 1.0 / max_value as f64;
+        let data: Vec<Color> = samples
+            .chunks_exact(channels)
+            .map(|c| {
+                if channels == 1 {
+                    let g = c[0] as f64 * scale;
+                    Color::new(g, g, g)
+                } else {
+                    Color::new(
+                        c[0] as f64 * scale,
+                        c[1] as f64 * scale,
+                        c[2] as f64 * scale,
+                    )
+                }
+            })
+            .collect();
+        Ok(Image::new(width, height, data))
+    }
+}
+
+// member functions
+impl Image {
+    // pixel lookup, coordinates are clamped to the image
+    pub fn pixel(&self, x: usize, y: usize) -> Color {
+        let x = x.min(self.width - 1);
+        let y = y.min(self.height - 1);
+        self.data[y * self.width + x]
+    }
+
+    // nearest pixel for texture coordinates, v = 0 at the bottom row
+    pub fn sample(&self, u: f64, v: f64) -> Color {
+        let u = u.clamp(0.0, 1.0);
+        let v = 1.0 - v.clamp(0.0, 1.0);
+        let x = (u * self.width as f64) as usize;
+        let y = (v * self.height as f64) as usize;
+        self.pixel(x, y)
+    }
+
+    // rec. 709 luminance of a pixel, for grayscale uses of color images
+    pub fn luminance(&self, x: usize, y: usize) -> f64 {
+        luminance(self.pixel(x, y))
+    }
+}
@@ -0,0 +1,103 @@
+use crate::aabb::Aabb;
+use crate::hittable::{HitRecord, Hittable};
+use crate::matrix::Mat4;
+use crate::ray::Ray;
+use crate::vec3::*;
+
+use std::sync::Arc;
+
+// places a shared object in the world through an affine transform,
+// so the same geometry can be reused without cloning it
+pub struct Instance {
+    pub object: Arc<dyn Hittable + Sync + Send>,
+    transform: Mat4,
+    inverse: Mat4,
+    bbox: Option<Aabb>,
+}
+
+// constructor and setter functions
+impl Instance {
+    pub fn new(object: Arc<dyn Hittable + Sync + Send>) -> Instance {
+        Instance::with_transform(object, Mat4::identity())
+    }
+
+    // panics if the transform is not invertible (e.g. a zero scale factor)
+    pub fn with_transform(object: Arc<dyn Hittable + Sync + Send>, transform: Mat4) -> Instance {
+        let inverse = transform
+            .inverse()
+            .expect("instance transform must be invertible");
+        let bbox = object.bounding_box().map(|b| {
+            let corners = b.corners().map(|c| transform.transform_point(c));
+            Aabb::from_points(&corners)
+        });
+        Instance {
+            object,
+            transform,
+            inverse,
+            bbox,
+        }
+    }
+
+    // each of the following applies its transform after the existing one
+    pub fn transformed(self, m: Mat4) -> Instance {
+        Instance::with_transform(self.object, m * self.transform)
+    }
+
+    pub fn translate(self, offset: Vec3) -> Instance {
+        self.transformed(Mat4::translation(offset))
+    }
+
+    pub fn rotate(self, axis: Vec3, degrees: f64) -> Instance {
+        self.transformed(Mat4::rotation(axis, degrees))
+    }
+
+    pub fn scale(self, factors: Vec3) -> Instance {
+        self.transformed(Mat4::scaling(factors))
+    }
+}
+
+// member access functions
+impl Instance {
+    pub fn transform(&self) -> Mat4 {
+        self.transform
+    }
+
+    pub fn inverse(&self) -> Mat4 {
+        self.inverse
+    }
+
+    // the direction is not renormalized, so t is the same in both spaces
+    fn object_ray(&self, r: &Ray) -> Ray {
+        Ray::new(
+            self.inverse.transform_point(r.origin()),
+            self.inverse.transform_vector(r.direction()),
+        )
+    }
+}
+
+impl Hittable for Instance {
+    fn hit(&self, r: &Ray, t_min: f64, t_max: f64) -> Option<HitRecord> {
+        let mut rec = self.object.hit(&self.object_ray(r), t_min, t_max)?;
+
+        let outward_normal: Vec3 = if rec.front_face {
+            rec.normal
+        } else {
+            -rec.normal
+        };
+        // normals transform by the inverse transpose
+        let world_normal = unit_vector(self.inverse.transpose().transform_vector(outward_normal));
+        rec.p = r.at(rec.t);
+        rec.set_face_normal(r, world_normal);
+        rec.dpdu = self.transform.transform_vector(rec.dpdu);
+        rec.dpdv = self.transform.transform_vector(rec.dpdv);
+        Some(rec)
+    }
+
+    fn bounding_box(&self) -> Option<Aabb> {
+        self.bbox
+    }
+
+    fn transmittance(&self, r: &Ray, t_min: f64, t_max: f64) -> f64 {
+        self.object.transmittance(&self.object_ray(r), t_min, t_max)
+    }
+}
@@ -0,0 +1,115 @@
+use crate::fresnel::fresnel_dielectric;
+use crate::hittable::HitRecord;
+use crate::material::{transmittance, Material, Output};
+use crate::ray::Ray;
+use crate::rtweekend::random_f64;
+use crate::vec3::*;
+
+use std::sync::Arc;
+
+// most bounces a path may take between the coat and the base
+const MAX_BOUNCES: usize = 32;
+
+// smooth dielectric coat, e.g. clear lacquer or varnish, over any base
+// material. the coat is treated as thin, so light bouncing between its
+// top surface and the base leaves from the point it entered
+pub struct Coated {
+    pub base: Arc<dyn Material + Sync + Send>,
+    pub ior: f64,
+    pub thickness: f64,
+    // beer-lambert absorption coefficient inside the coat, per channel
+    pub absorption: Color,
+}
+
+// constructor functions
+impl Coated {
+    pub fn new(
+        base: Arc<dyn Material + Sync + Send>,
+        ior: f64,
+        thickness: f64,
+        absorption: Color,
+    ) -> Coated {
+        Coated {
+            base,
+            ior,
+            thickness,
+            absorption,
+        }
+    }
+
+    // colorless coat
+    pub fn clear(base: Arc<dyn Material + Sync + Send>, ior: f64) -> Coated {
+        Coated::new(base, ior, 0.0, Color::new(0.0, 0.0, 0.0))
+    }
+}
+
+// member functions
+impl Coated {
+    // absorption along one crossing of the coat at the given angle
+    fn crossing(&self, cos_theta: f64) -> Color {
+        transmittance(self.absorption, self.thickness / cos_theta.max(1e-6))
+    }
+}
+
+impl Material for Coated {
+    fn scatter(&self, r_in: &Ray, rec: &HitRecord) -> Option<Output> {
+        // the coat only covers the outside
+        if !rec.front_face {
+            return self.base.scatter(r_in, rec);
+        }
+
+        let n = rec.normal;
+        let unit_direction: Vec3 = unit_vector(r_in.direction());
+        let cos_theta: f64 = dot(&-unit_direction, &n).min(1.0);
+
+        // specular reflection off the coat, chosen by the fresnel term so
+        // it cancels out of the weight
+        if random_f64() < fresnel_dielectric(cos_theta, self.ior) {
+            return Some(Output {
+                attenuation: Color::new(1.0, 1.0, 1.0),
+                scattered: Ray::new(rec.p, reflect(unit_direction, n)),
+            });
+        }
+
+        // random walk between the base and the underside of the coat,
+        // internal reflections send light back down to the base
+        let mut direction: Vec3 = refract(unit_direction, n, 1.0 / self.ior);
+        let mut attenuation: Color = Color::new(1.0, 1.0, 1.0);
+        for _ in 0..MAX_BOUNCES {
+            attenuation = attenuation * self.crossing(dot(&-direction, &n));
+
+            let mut inner: Ray = *r_in;
+            inner.orig = rec.p;
+            inner.dir = direction;
+            let out = self.base.scatter(&inner, rec)?;
+            attenuation = attenuation * out.attenuation;
+            direction = unit_vector(out.scattered.direction());
+            let cos_up: f64 = dot(&direction, &n);
+            if cos_up <= 0.0 {
+                // transmitted through the base
+                return Some(Output {
+                    attenuation,
+                    scattered: Ray::new(rec.p, direction),
+                });
+            }
+
+            attenuation = attenuation * self.crossing(cos_up);
+            if random_f64() >= fresnel_dielectric(cos_up, 1.0 / self.ior) {
+                return Some(Output {
+                    attenuation,
+                    scattered: Ray::new(rec.p, refract(direction, -n, self.ior)),
+                });
+            }
+            direction = reflect(direction, n);
+        }
+        None
+    }
+
+    fn dispersive(&self) -> bool {
+        self.base.dispersive()
+    }
+
+    fn emitted(&self, rec: &HitRecord) -> Color {
+        self.base.emitted(rec)
+    }
+}
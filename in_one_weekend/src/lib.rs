@@ -1,12 +1,46 @@
+pub mod aabb;
+pub mod alpha_mask;
+pub mod aperture;
 pub mod camera;
 pub mod clio;
 pub mod color;
+pub mod cone;
+pub mod constant_medium;
+pub mod csg;
+pub mod cylinder;
+pub mod density_grid;
+pub mod disk;
+pub mod film;
+pub mod fresnel;
+pub mod grid_medium;
+pub mod heightfield;
 pub mod hittable;
 pub mod hittable_list;
+pub mod image;
+pub mod instance;
+pub mod layered;
 pub mod material;
+pub mod matrix;
+pub mod microfacet;
+pub mod normal_map;
+pub mod npr;
+pub mod onb;
+pub mod panoramic;
+pub mod perlin;
+pub mod plane;
+pub mod polynomial;
+pub mod principled;
+pub mod quad;
 pub mod ray;
 pub mod rtweekend;
 pub mod scenes;
+pub mod sdf;
+pub mod spectral;
 pub mod sphere;
+pub mod stereo;
+pub mod subsurface;
+pub mod texture;
+pub mod thin_film;
 pub mod threaded;
+pub mod torus;
 pub mod vec3;
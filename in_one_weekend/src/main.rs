@@ -1,17 +1,5 @@
-mod camera;
-mod clio;
-mod color;
-mod hittable;
-mod hittable_list;
-mod material;
-mod ray;
-mod rtweekend;
-mod scenes;
-mod sphere;
-mod threaded;
-mod vec3;
-
-use scenes::SceneSettings;
+use in_one_weekend::scenes::{self, SceneSettings};
+use in_one_weekend::threaded;
 
 fn main() {
     // get scene, cam, and settings
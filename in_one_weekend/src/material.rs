@@ -1,10 +1,19 @@
+use crate::color::luminance;
+use crate::fresnel::{fresnel_dielectric, ComplexIor, ConductorFresnel};
 use crate::hittable::HitRecord;
+use crate::microfacet::TrowbridgeReitz;
+use crate::onb::Onb;
 use crate::ray::Ray;
-use crate::rtweekend::random_f64;
+use crate::rtweekend::{degrees_to_radians, random_f64, PI};
+use crate::spectral::Dispersion;
+use crate::texture::Texture;
+use crate::thin_film::ThinFilm;
 use crate::vec3::{
     dot, random_in_unit_sphere, random_unit_vector, reflect, refract, unit_vector, Color, Vec3,
 };
 
+use std::sync::Arc;
+
 pub struct Output {
     pub attenuation: Color,
     pub scattered: Ray,
@@ -12,6 +21,18 @@ pub struct Output {
 
 pub trait Material {
     fn scatter(&self, r_in: &Ray, rec: &HitRecord) -> Option<Output>;
+
+    // whether scattering depends on the ray's wavelength, in which case
+    // spectral rendering follows only the hero wavelength from here on
+    fn dispersive(&self) -> bool {
+        false
+    }
+
+    // light given off by the surface itself, added on top of what it
+    // scatters
+    fn emitted(&self, _rec: &HitRecord) -> Color {
+        Color::new(0.0, 0.0, 0.0)
+    }
 }
 
 pub struct Lambertian {
@@ -40,6 +61,118 @@ impl Material for Lambertian {
     }
 }
 
+// cosine weighted direction about +z, in the local shading frame. its
+// pdf is cos / pi, so a brdf f sampled this way weighs f * pi
+pub(crate) fn sample_cosine_local() -> Vec3 {
+    let d = Vec3::new(0.0, 0.0, 1.0) + random_unit_vector();
+    if d.near_zero() {
+        Vec3::new(0.0, 0.0, 1.0)
+    } else {
+        unit_vector(d)
+    }
+}
+
+// rough diffuse surface of lambertian v-grooves whose slopes have a
+// standard deviation of sigma degrees. flatter and more retro reflective
+// than lambertian, like clay or plaster
+pub struct OrenNayar {
+    pub albedo: Color,
+    pub sigma: f64,
+}
+
+impl OrenNayar {
+    pub fn new(albedo: Color, sigma: f64) -> OrenNayar {
+        OrenNayar { albedo, sigma }
+    }
+
+    // the a and b terms of the qualitative model
+    pub fn coefficients(&self) -> (f64, f64) {
+        let sigma2 = degrees_to_radians(self.sigma).powi(2);
+        let a = 1.0 - sigma2 / (2.0 * (sigma2 + 0.33));
+        let b = 0.45 * sigma2 / (sigma2 + 0.09);
+        (a, b)
+    }
+}
+
+impl Material for OrenNayar {
+    fn scatter(&self, r_in: &Ray, rec: &HitRecord) -> Option<Output> {
+        let uvw = Onb::build_from_w(rec.normal);
+        let wo = uvw.to_local(-unit_vector(r_in.direction()));
+        let wi = sample_cosine_local();
+
+        let sin_o = (1.0 - wo.z() * wo.z()).max(0.0).sqrt();
+        let sin_i = (1.0 - wi.z() * wi.z()).max(0.0).sqrt();
+        let cos_o = wo.z().abs();
+        let cos_i = wi.z().abs();
+
+        // cosine of the azimuth between the two directions
+        let max_cos = if sin_i > 1e-4 && sin_o > 1e-4 {
+            ((wi.x() * wo.x() + wi.y() * wo.y()) / (sin_i * sin_o)).max(0.0)
+        } else {
+            0.0
+        };
+        let (sin_alpha, tan_beta) = if cos_i > cos_o {
+            (sin_o, sin_i / cos_i)
+        } else {
+            (sin_i, sin_o / cos_o.max(1e-6))
+        };
+
+        let (a, b) = self.coefficients();
+        Some(Output {
+            attenuation: (a + b * max_cos * sin_alpha * tan_beta) * self.albedo,
+            scattered: Ray::new(rec.p, uvw.local_vec(wi)),
+        })
+    }
+}
+
+// diffuse base under a soft grazing sheen from fibers, for velvet, satin
+// and other cloth. uses the "charlie" sheen distribution of estevez and
+// kulla with neubelt and pettineo's visibility term
+pub struct Velvet {
+    pub albedo: Color,
+    pub sheen: Color,
+    pub roughness: f64,
+}
+
+impl Velvet {
+    pub fn new(albedo: Color, sheen: Color, roughness: f64) -> Velvet {
+        Velvet {
+            albedo,
+            sheen,
+            roughness,
+        }
+    }
+
+    pub fn sheen_distribution(&self, cos_theta_h: f64) -> f64 {
+        let inv_alpha = 1.0 / self.roughness.clamp(0.01, 1.0);
+        let sin_theta_h = (1.0 - cos_theta_h * cos_theta_h).max(0.0).sqrt();
+        (2.0 + inv_alpha) * sin_theta_h.powf(inv_alpha) / (2.0 * PI)
+    }
+}
+
+impl Material for Velvet {
+    fn scatter(&self, r_in: &Ray, rec: &HitRecord) -> Option<Output> {
+        let uvw = Onb::build_from_w(rec.normal);
+        let wo = uvw.to_local(-unit_vector(r_in.direction()));
+        let wi = sample_cosine_local();
+        let cos_o = wo.z().max(0.0);
+        let cos_i = wi.z();
+
+        let wh = wi + wo;
+        let d = if wh.near_zero() {
+            0.0
+        } else {
+            self.sheen_distribution(unit_vector(wh).z())
+        };
+        let visibility = 1.0 / (4.0 * (cos_i + cos_o - cos_i * cos_o)).max(1e-6);
+
+        Some(Output {
+            attenuation: self.albedo + (PI * d * visibility * cos_i) * self.sheen,
+            scattered: Ray::new(rec.p, uvw.local_vec(wi)),
+        })
+    }
+}
+
 pub struct Metal {
     pub albedo: Color,
     pub fuzz: f64,
@@ -68,17 +201,106 @@ impl Material for Metal {
     }
 }
 
+// polished metal with angle dependent reflectance from its complex
+// index of refraction, so it tints towards white at grazing angles
+pub struct Conductor {
+    pub ior: ComplexIor,
+    pub thin_film: Option<ThinFilm>,
+}
+
+impl Conductor {
+    pub fn new(ior: ComplexIor) -> Conductor {
+        Conductor {
+            ior,
+            thin_film: None,
+        }
+    }
+
+    // metal under an iridescent film, e.g. oil or temper oxide
+    pub fn with_thin_film(ior: ComplexIor, film: ThinFilm) -> Conductor {
+        Conductor {
+            ior,
+            thin_film: Some(film),
+        }
+    }
+}
+
+impl Material for Conductor {
+    fn scatter(&self, r_in: &Ray, rec: &HitRecord) -> Option<Output> {
+        let unit_direction: Vec3 = unit_vector(r_in.direction());
+        let cos_theta: f64 = dot(&-unit_direction, &rec.normal).min(1.0);
+        let attenuation: Color = match self.thin_film {
+            Some(film) => film.reflectance_conductor(cos_theta, &self.ior, r_in.wavelength),
+            None => self.ior.reflectance(cos_theta),
+        };
+        Some(Output {
+            attenuation,
+            scattered: Ray::new(rec.p, reflect(unit_direction, rec.normal)),
+        })
+    }
+
+    fn dispersive(&self) -> bool {
+        self.thin_film.is_some()
+    }
+}
+
 pub struct Dielectric {
     pub ir: f64,
+    // beer-lambert absorption coefficient per unit length, per channel
+    pub absorption: Color,
+    // wavelength dependent index, replacing `ir` in spectral renders
+    pub dispersion: Option<Dispersion>,
+    // iridescent coating on the outside of the surface
+    pub thin_film: Option<ThinFilm>,
 }
 
 impl Dielectric {
     pub fn new(index_of_refraction: f64) -> Dielectric {
+        Dielectric::with_absorption(index_of_refraction, Color::new(0.0, 0.0, 0.0))
+    }
+
+    pub fn with_absorption(index_of_refraction: f64, absorption: Color) -> Dielectric {
         Dielectric {
             ir: index_of_refraction,
+            absorption,
+            dispersion: None,
+            thin_film: None,
         }
     }
 
+    pub fn with_dispersion(dispersion: Dispersion) -> Dielectric {
+        Dielectric {
+            dispersion: Some(dispersion),
+            ..Dielectric::new(dispersion.ior_d())
+        }
+    }
+
+    // a shell of film with air on both sides, e.g. a soap bubble
+    pub fn bubble(film: ThinFilm) -> Dielectric {
+        Dielectric {
+            thin_film: Some(film),
+            ..Dielectric::new(1.0)
+        }
+    }
+
+    // absorption chosen so light keeps `color` of its energy after
+    // travelling `distance` through the material
+    pub fn with_transmission_color(
+        index_of_refraction: f64,
+        color: Color,
+        distance: f64,
+    ) -> Dielectric {
+        let coefficient = |c: f64| -c.max(1e-6).ln() / distance;
+        Dielectric::with_absorption(
+            index_of_refraction,
+            Color::new(
+                coefficient(color.x()),
+                coefficient(color.y()),
+                coefficient(color.z()),
+            ),
+        )
+    }
+
     fn reflectance(&self, cosine: f64, ref_idx: f64) -> f64 {
         // use schlick's approximation for reflectance
         let r0 = ((1.0 - ref_idx) / (1.0 + ref_idx)).powi(2);
@@ -86,14 +308,28 @@ impl Dielectric {
     }
 }
 
+// beer-lambert falloff over a path of the given length
+pub fn transmittance(absorption: Color, distance: f64) -> Color {
+    Color::new(
+        (-absorption.x() * distance).exp(),
+        (-absorption.y() * distance).exp(),
+        (-absorption.z() * distance).exp(),
+    )
+}
+
 impl Material for Dielectric {
     fn scatter(&self, r_in: &Ray, rec: &HitRecord) -> Option<Output> {
-        let attenuation: Color = Color::new(1.0, 1.0, 1.0);
-        let refraction_ratio: f64 = if rec.front_face {
-            1.0 / self.ir
+        // hitting the inside of the surface ends a path through the material
+        let mut attenuation: Color = if rec.front_face {
+            Color::new(1.0, 1.0, 1.0)
         } else {
-            self.ir
+            transmittance(self.absorption, rec.t * r_in.direction().length())
+        };
+        let ir: f64 = match (self.dispersion, r_in.wavelength) {
+            (Some(dispersion), Some(lambda)) => dispersion.ior(lambda),
+            _ => self.ir,
         };
+        let refraction_ratio: f64 = if rec.front_face { 1.0 / ir } else { ir };
 
         let unit_direction: Vec3 = unit_vector(r_in.direction());
         let cos_theta: f64 = dot(&-unit_direction, &rec.normal).min(1.0);
@@ -101,8 +337,32 @@ impl Material for Dielectric {
 
         let cannot_refract: bool = refraction_ratio * sin_theta > 1.0;
 
-        let schlick_approx: bool = self.reflectance(cos_theta, refraction_ratio) > random_f64();
-        let direction: Vec3 = if cannot_refract || schlick_approx {
+        let reflects: bool = if cannot_refract {
+            true
+        } else if let Some(film) = self.thin_film {
+            // the film reflects each channel differently, so pick a branch
+            // by the mean and reweight the channels
+            let (outside, inside) = if rec.front_face { (1.0, ir) } else { (ir, 1.0) };
+            let reflectance: Color = film.reflectance(
+                cos_theta,
+                outside,
+                Color::new(inside, inside, inside),
+                Color::new(0.0, 0.0, 0.0),
+                r_in.wavelength,
+            );
+            let p_reflect = (reflectance.x() + reflectance.y() + reflectance.z()) / 3.0;
+            if random_f64() < p_reflect {
+                attenuation = attenuation * reflectance / p_reflect;
+                true
+            } else {
+                attenuation =
+                    attenuation * (Color::new(1.0, 1.0, 1.0) - reflectance) / (1.0 - p_reflect);
+                false
+            }
+        } else {
+            self.reflectance(cos_theta, refraction_ratio) > random_f64()
+        };
+        let direction: Vec3 = if reflects {
             reflect(unit_direction, rec.normal)
         } else {
             refract(unit_direction, rec.normal, refraction_ratio)
@@ -113,4 +373,269 @@ impl Material for Dielectric {
             scattered,
         })
     }
+
+    fn dispersive(&self) -> bool {
+        self.dispersion.is_some() || self.thin_film.is_some()
+    }
+}
+
+// phase function scattering uniformly over the sphere, for use inside media
+pub struct Isotropic {
+    pub albedo: Color,
+}
+
+impl Isotropic {
+    pub fn new(albedo: Color) -> Isotropic {
+        Isotropic { albedo }
+    }
+}
+
+impl Material for Isotropic {
+    fn scatter(&self, _r_in: &Ray, rec: &HitRecord) -> Option<Output> {
+        Some(Output {
+            attenuation: self.albedo,
+            scattered: Ray::new(rec.p, random_unit_vector()),
+        })
+    }
+}
+
+// anisotropic phase function, g > 0 favors forward scattering,
+// g < 0 back scattering and g = 0 is isotropic
+pub struct HenyeyGreenstein {
+    pub albedo: Color,
+    pub g: f64,
+}
+
+impl HenyeyGreenstein {
+    pub fn new(albedo: Color, g: f64) -> HenyeyGreenstein {
+        HenyeyGreenstein {
+            albedo,
+            g: g.clamp(-0.99, 0.99),
+        }
+    }
+
+    // phase function value for the cosine of the angle between the
+    // incoming and outgoing propagation directions
+    pub fn phase(&self, cos_theta: f64) -> f64 {
+        let denom = 1.0 + self.g * self.g - 2.0 * self.g * cos_theta;
+        (1.0 - self.g * self.g) / (4.0 * PI * denom * denom.sqrt())
+    }
+
+    // samples the cosine of the scattering angle proportional to the phase function
+    pub fn sample_cos_theta(&self, xi: f64) -> f64 {
+        let g = self.g;
+        if g.abs() < 1e-3 {
+            return 1.0 - 2.0 * xi;
+        }
+        let sqr_term = (1.0 - g * g) / (1.0 + g - 2.0 * g * xi);
+        ((1.0 + g * g - sqr_term * sqr_term) / (2.0 * g)).clamp(-1.0, 1.0)
+    }
+}
+
+impl Material for HenyeyGreenstein {
+    fn scatter(&self, r_in: &Ray, rec: &HitRecord) -> Option<Output> {
+        let cos_theta = self.sample_cos_theta(random_f64());
+        let sin_theta = (1.0 - cos_theta * cos_theta).max(0.0).sqrt();
+        let phi = 2.0 * PI * random_f64();
+
+        // the phase function is sampled exactly, so the weight is the albedo
+        let uvw = Onb::build_from_w(r_in.direction());
+        let direction = uvw.local(sin_theta * phi.cos(), sin_theta * phi.sin(), cos_theta);
+        Some(Output {
+            attenuation: self.albedo,
+            scattered: Ray::new(rec.p, direction),
+        })
+    }
+}
+
+// rough metal with a ggx microfacet brdf. energy lost to
+// masking-shadowing is not recovered
+pub struct RoughConductor {
+    pub fresnel: ConductorFresnel,
+    pub distribution: TrowbridgeReitz,
+}
+
+impl RoughConductor {
+    // tinted by its reflectance at normal incidence
+    pub fn new(f0: Color, roughness: f64) -> RoughConductor {
+        RoughConductor {
+            fresnel: ConductorFresnel::Schlick(f0),
+            distribution: TrowbridgeReitz::from_roughness(roughness),
+        }
+    }
+
+    // driven by measured optical constants, e.g. `ComplexIor::gold()`
+    pub fn from_ior(ior: ComplexIor, roughness: f64) -> RoughConductor {
+        RoughConductor {
+            fresnel: ConductorFresnel::Complex(ior),
+            distribution: TrowbridgeReitz::from_roughness(roughness),
+        }
+    }
+}
+
+impl Material for RoughConductor {
+    fn scatter(&self, r_in: &Ray, rec: &HitRecord) -> Option<Output> {
+        let uvw = Onb::build_from_w(rec.normal);
+        let wo = uvw.to_local(-unit_vector(r_in.direction()));
+        if wo.z() <= 0.0 {
+            return None;
+        }
+
+        let wm = self.distribution.sample_wm(wo, random_f64(), random_f64());
+        let wi = reflect(-wo, wm);
+        if wi.z() <= 0.0 {
+            return None;
+        }
+
+        // f * cos / pdf reduces to F * G2 / G1 under visible normal sampling
+        let fresnel = self.fresnel.reflectance_at(dot(&wo, &wm), r_in.wavelength);
+        let weight = self.distribution.g(wo, wi) / self.distribution.g1(wo);
+        Some(Output {
+            attenuation: weight * fresnel,
+            scattered: Ray::new(rec.p, uvw.local_vec(wi)),
+        })
+    }
+
+    fn dispersive(&self) -> bool {
+        matches!(self.fresnel, ConductorFresnel::ThinFilm(..))
+    }
+}
+
+// frosted glass with ggx microfacet reflection and transmission
+pub struct RoughDielectric {
+    pub ir: f64,
+    pub distribution: TrowbridgeReitz,
+    pub absorption: Color,
+}
+
+impl RoughDielectric {
+    pub fn new(index_of_refraction: f64, roughness: f64) -> RoughDielectric {
+        RoughDielectric::with_absorption(index_of_refraction, roughness, Color::new(0.0, 0.0, 0.0))
+    }
+
+    pub fn with_absorption(
+        index_of_refraction: f64,
+        roughness: f64,
+        absorption: Color,
+    ) -> RoughDielectric {
+        RoughDielectric {
+            ir: index_of_refraction,
+            distribution: TrowbridgeReitz::from_roughness(roughness),
+            absorption,
+        }
+    }
+}
+
+impl Material for RoughDielectric {
+    fn scatter(&self, r_in: &Ray, rec: &HitRecord) -> Option<Output> {
+        // relative index of the side being entered over the side left
+        let eta: f64 = if rec.front_face {
+            self.ir
+        } else {
+            1.0 / self.ir
+        };
+
+        let uvw = Onb::build_from_w(rec.normal);
+        let wo = uvw.to_local(-unit_vector(r_in.direction()));
+        if wo.z() <= 0.0 {
+            return None;
+        }
+        let wm = self.distribution.sample_wm(wo, random_f64(), random_f64());
+
+        // choose reflection or transmission by the fresnel term, so it
+        // cancels out of the weight
+        let cos_theta = dot(&wo, &wm);
+        let wi = if random_f64() < fresnel_dielectric(cos_theta, eta) {
+            let wi = reflect(-wo, wm);
+            if wi.z() <= 0.0 {
+                return None;
+            }
+            wi
+        } else {
+            let wi = refract(-wo, wm, 1.0 / eta);
+            if wi.z() >= 0.0 {
+                return None;
+            }
+            wi
+        };
+
+        let weight = self.distribution.g(wo, wi) / self.distribution.g1(wo);
+        let absorbed = if rec.front_face {
+            Color::new(1.0, 1.0, 1.0)
+        } else {
+            transmittance(self.absorption, rec.t * r_in.direction().length())
+        };
+        Some(Output {
+            attenuation: weight * absorbed,
+            scattered: Ray::new(rec.p, uvw.local_vec(wi)),
+        })
+    }
+}
+
+// how much of the second material a mix shows
+pub enum MixWeight {
+    Constant(f64),
+    // luminance of a texture at the hit, e.g. a rust or dirt mask
+    Mask(Arc<dyn Texture + Sync + Send>),
+}
+
+// picks one of two materials per scatter, the second with probability
+// given by the weight at the hit
+pub struct Mix {
+    pub first: Arc<dyn Material + Sync + Send>,
+    pub second: Arc<dyn Material + Sync + Send>,
+    pub weight: MixWeight,
+}
+
+impl Mix {
+    pub fn new(
+        first: Arc<dyn Material + Sync + Send>,
+        second: Arc<dyn Material + Sync + Send>,
+        weight: f64,
+    ) -> Mix {
+        Mix {
+            first,
+            second,
+            weight: MixWeight::Constant(weight),
+        }
+    }
+
+    pub fn with_mask(
+        first: Arc<dyn Material + Sync + Send>,
+        second: Arc<dyn Material + Sync + Send>,
+        mask: Arc<dyn Texture + Sync + Send>,
+    ) -> Mix {
+        Mix {
+            first,
+            second,
+            weight: MixWeight::Mask(mask),
+        }
+    }
+
+    pub fn weight_at(&self, rec: &HitRecord) -> f64 {
+        let w = match &self.weight {
+            MixWeight::Constant(w) => *w,
+            MixWeight::Mask(mask) => luminance(mask.value(rec.u, rec.v, &rec.p)),
+        };
+        w.clamp(0.0, 1.0)
+    }
+}
+
+impl Material for Mix {
+    fn scatter(&self, r_in: &Ray, rec: &HitRecord) -> Option<Output> {
+        if random_f64() < self.weight_at(rec) {
+            self.second.scatter(r_in, rec)
+        } else {
+            self.first.scatter(r_in, rec)
+        }
+    }
+
+    fn dispersive(&self) -> bool {
+        self.first.dispersive() || self.second.dispersive()
+    }
+
+    fn emitted(&self, rec: &HitRecord) -> Color {
+        let w = self.weight_at(rec);
+        (1.0 - w) * self.first.emitted(rec) + w * self.second.emitted(rec)
+    }
 }
@@ -0,0 +1,115 @@
+use crate::rtweekend::PI;
+use crate::vec3::*;
+
+// trowbridge-reitz (ggx) microfacet distribution. all directions are in
+// the local shading frame, where the macro surface normal is +z
+#[derive(Debug, Copy, Clone)]
+pub struct TrowbridgeReitz {
+    pub alpha_x: f64,
+    pub alpha_y: f64,
+}
+
+// constructor functions
+impl TrowbridgeReitz {
+    pub fn new(alpha_x: f64, alpha_y: f64) -> TrowbridgeReitz {
+        // keep a sliver of roughness so the distribution stays finite
+        TrowbridgeReitz {
+            alpha_x: alpha_x.max(1e-4),
+            alpha_y: alpha_y.max(1e-4),
+        }
+    }
+
+    // isotropic distribution from perceptual roughness in [0, 1]
+    pub fn from_roughness(roughness: f64) -> TrowbridgeReitz {
+        let alpha = roughness.clamp(0.0, 1.0).powi(2);
+        TrowbridgeReitz::new(alpha, alpha)
+    }
+}
+
+// member functions
+impl TrowbridgeReitz {
+    // density of microfacet normals wm
+    pub fn d(&self, wm: Vec3) -> f64 {
+        let cos2_theta = wm.z() * wm.z();
+        if cos2_theta <= 0.0 {
+            return 0.0;
+        }
+        let sin2_theta = (1.0 - cos2_theta).max(0.0);
+        let tan2_theta = sin2_theta / cos2_theta;
+        let (cos2_phi, sin2_phi) = if sin2_theta > 0.0 {
+            (wm.x() * wm.x() / sin2_theta, wm.y() * wm.y() / sin2_theta)
+        } else {
+            (1.0, 0.0)
+        };
+        let e = tan2_theta
+            * (cos2_phi / (self.alpha_x * self.alpha_x) + sin2_phi / (self.alpha_y * self.alpha_y));
+        1.0 / (PI * self.alpha_x * self.alpha_y * cos2_theta * cos2_theta * (1.0 + e) * (1.0 + e))
+    }
+
+    // smith auxiliary function for direction w
+    pub fn lambda(&self, w: Vec3) -> f64 {
+        let cos2_theta = w.z() * w.z();
+        if cos2_theta <= 0.0 {
+            return f64::INFINITY;
+        }
+        let alpha2 = w.x() * w.x() * self.alpha_x * self.alpha_x
+            + w.y() * w.y() * self.alpha_y * self.alpha_y;
+        // alpha^2 tan^2(theta), with the anisotropic alpha folded in
+        let alpha2_tan2_theta = alpha2 / cos2_theta;
+        ((1.0 + alpha2_tan2_theta).sqrt() - 1.0) / 2.0
+    }
+
+    // masking for a single direction
+    pub fn g1(&self, w: Vec3) -> f64 {
+        1.0 / (1.0 + self.lambda(w))
+    }
+
+    // height correlated masking-shadowing for a pair of directions
+    pub fn g(&self, wo: Vec3, wi: Vec3) -> f64 {
+        1.0 / (1.0 + self.lambda(wo) + self.lambda(wi))
+    }
+
+    // density of normals visible from wo, the pdf of sample_wm
+    pub fn visible_d(&self, wo: Vec3, wm: Vec3) -> f64 {
+        if wo.z() == 0.0 {
+            return 0.0;
+        }
+        self.g1(wo) / wo.z().abs() * self.d(wm) * dot(&wo, &wm).abs()
+    }
+
+    // samples a microfacet normal visible from wo (heitz 2018),
+    // u1 and u2 are uniform random numbers in [0, 1)
+    pub fn sample_wm(&self, wo: Vec3, u1: f64, u2: f64) -> Vec3 {
+        // flip wo to the upper hemisphere and stretch it to unit roughness
+        let flip = if wo.z() < 0.0 { -1.0 } else { 1.0 };
+        let vh = unit_vector(Vec3::new(
+            self.alpha_x * wo.x() * flip,
+            self.alpha_y * wo.y() * flip,
+            wo.z() * flip,
+        ));
+
+        // orthonormal basis around vh
+        let lensq = vh.x() * vh.x() + vh.y() * vh.y();
+        let t1 = if lensq > 0.0 {
+            Vec3::new(-vh.y(), vh.x(), 0.0) / lensq.sqrt()
+        } else {
+            Vec3::new(1.0, 0.0, 0.0)
+        };
+        let t2 = cross(&vh, &t1);
+
+        // uniform disk sample, warped to the visible half of the disk
+        let r = u1.sqrt();
+        let phi = 2.0 * PI * u2;
+        let p1 = r * phi.cos();
+        let s = 0.5 * (1.0 + vh.z());
+        let p2 = (1.0 - s) * (1.0 - p1 * p1).max(0.0).sqrt() + s * r * phi.sin();
+
+        // project onto the hemisphere and unstretch
+        let nh = p1 * t1 + p2 * t2 + (1.0 - p1 * p1 - p2 * p2).max(0.0).sqrt() * vh;
+        unit_vector(Vec3::new(
+            self.alpha_x * nh.x(),
+            self.alpha_y * nh.y(),
+            nh.z().max(1e-6),
+        ))
+    }
+}
@@ -0,0 +1,161 @@
+use crate::color::luminance;
+use crate::hittable::HitRecord;
+use crate::material::{Material, Output};
+use crate::ray::Ray;
+use crate::texture::Texture;
+use crate::vec3::*;
+
+use std::sync::Arc;
+
+// step in surface coordinates for finite differences of bump maps
+const BUMP_DELTA: f64 = 1e-3;
+
+// shades the base material with a perturbed normal. the hit keeps its
+// geometric normal for front_face and the ray origin, and scattering that
+// crosses the geometric surface on the wrong side is dropped
+fn scatter_with_normal(
+    base: &(dyn Material + Sync + Send),
+    r_in: &Ray,
+    rec: &HitRecord,
+    shading_normal: Vec3,
+) -> Option<Output> {
+    let geometric = rec.normal;
+    // a normal facing away from the viewer cannot be shaded, use the
+    // surface as is
+    if dot(&shading_normal, &r_in.direction()) >= 0.0 {
+        return base.scatter(r_in, rec);
+    }
+
+    let mut shaded = rec.clone();
+    shaded.normal = shading_normal;
+    let out = base.scatter(r_in, &shaded)?;
+    let d = out.scattered.direction();
+    if dot(&d, &shading_normal).signum() != dot(&d, &geometric).signum() {
+        return None;
+    }
+    Some(out)
+}
+
+// tangent space normal map: rgb in [0, 1] encodes a normal in the frame
+// (dpdu, dpdv, n), flat being (0.5, 0.5, 1)
+pub struct NormalMap {
+    pub base: Arc<dyn Material + Sync + Send>,
+    pub map: Arc<dyn Texture + Sync + Send>,
+    // scales the tangential part, 0 disables the map
+    pub strength: f64,
+}
+
+// constructor functions
+impl NormalMap {
+    pub fn new(
+        base: Arc<dyn Material + Sync + Send>,
+        map: Arc<dyn Texture + Sync + Send>,
+        strength: f64,
+    ) -> NormalMap {
+        NormalMap {
+            base,
+            map,
+            strength,
+        }
+    }
+}
+
+// member functions
+impl NormalMap {
+    pub fn shading_normal(&self, rec: &HitRecord) -> Vec3 {
+        let c = self.map.value(rec.u, rec.v, &rec.p);
+        let n = Vec3::new(
+            self.strength * (2.0 * c.x() - 1.0),
+            self.strength * (2.0 * c.y() - 1.0),
+            (2.0 * c.z() - 1.0).max(1e-3),
+        );
+        unit_vector(rec.tangent_frame().local_vec(n))
+    }
+}
+
+impl Material for NormalMap {
+    fn scatter(&self, r_in: &Ray, rec: &HitRecord) -> Option<Output> {
+        scatter_with_normal(self.base.as_ref(), r_in, rec, self.shading_normal(rec))
+    }
+
+    fn dispersive(&self) -> bool {
+        self.base.dispersive()
+    }
+
+    fn emitted(&self, rec: &HitRecord) -> Color {
+        self.base.emitted(rec)
+    }
+}
+
+// grayscale height map displacing the surface along its normal by
+// `scale` times the texture's luminance, for shading only
+pub struct BumpMap {
+    pub base: Arc<dyn Material + Sync + Send>,
+    pub height: Arc<dyn Texture + Sync + Send>,
+    pub scale: f64,
+}
+
+// constructor functions
+impl BumpMap {
+    pub fn new(
+        base: Arc<dyn Material + Sync + Send>,
+        height: Arc<dyn Texture + Sync + Send>,
+        scale: f64,
+    ) -> BumpMap {
+        BumpMap {
+            base,
+            height,
+            scale,
+        }
+    }
+}
+
+// member functions
+impl BumpMap {
+    fn displacement(&self, u: f64, v: f64, p: &Point3) -> f64 {
+        self.scale * luminance(self.height.value(u, v, p))
+    }
+
+    pub fn shading_normal(&self, rec: &HitRecord) -> Vec3 {
+        let n = rec.normal;
+        // shapes without uv derivatives get unit tangents
+        let (dpdu, dpdv) = if rec.dpdu.near_zero() || rec.dpdv.near_zero() {
+            let frame = rec.tangent_frame();
+            (frame.u(), frame.v())
+        } else {
+            (rec.dpdu, rec.dpdv)
+        };
+
+        let d = self.displacement(rec.u, rec.v, &rec.p);
+        let du = (self.displacement(rec.u + BUMP_DELTA, rec.v, &(rec.p + BUMP_DELTA * dpdu)) - d)
+            / BUMP_DELTA;
+        let dv = (self.displacement(rec.u, rec.v + BUMP_DELTA, &(rec.p + BUMP_DELTA * dpdv)) - d)
+            / BUMP_DELTA;
+
+        // tangents of the displaced surface, ignoring the curvature term
+        let bumped = cross(&(dpdu + du * n), &(dpdv + dv * n));
+        if bumped.near_zero() {
+            return n;
+        }
+        let bumped = unit_vector(bumped);
+        if dot(&bumped, &n) < 0.0 {
+            -bumped
+        } else {
+            bumped
+        }
+    }
+}
+
+impl Material for BumpMap {
+    fn scatter(&self, r_in: &Ray, rec: &HitRecord) -> Option<Output> {
+        scatter_with_normal(self.base.as_ref(), r_in, rec, self.shading_normal(rec))
+    }
+
+    fn dispersive(&self) -> bool {
+        self.base.dispersive()
+    }
+
+    fn emitted(&self, rec: &HitRecord) -> Color {
+        self.base.emitted(rec)
+    }
+}
@@ -0,0 +1,256 @@
+use crate::color::write_color;
+use crate::film::{film_coordinates, sample_pixel};
+use crate::hittable::HitRecord;
+use crate::hittable_list::HittableList;
+use crate::material::{Material, Output};
+use crate::ray::Ray;
+use crate::rtweekend::{degrees_to_radians, INFINITY};
+use crate::scenes::SceneSettings;
+use crate::vec3::*;
+
+use std::thread;
+
+// cel shading: a flat lit color and a shadow color, with the lighting
+// from a single direction quantized into bands. the surface lights
+// itself and scatters nothing, so it reads as a flat illustration
+pub struct Toon {
+    pub lit: Color,
+    pub shadow: Color,
+    // unit direction towards the light
+    pub light: Vec3,
+    pub bands: u32,
+}
+
+// constructor functions
+impl Toon {
+    pub fn new(lit: Color, shadow: Color, light: Vec3, bands: u32) -> Toon {
+        Toon {
+            lit,
+            shadow,
+            light: unit_vector(light),
+            bands,
+        }
+    }
+}
+
+// member functions
+impl Toon {
+    pub fn shade(&self, normal: Vec3) -> Color {
+        let intensity: f64 = dot(&unit_vector(normal), &self.light).max(0.0);
+        let level: f64 = if self.bands <= 1 {
+            1.0
+        } else {
+            let band = ((intensity * self.bands as f64) as u32).min(self.bands - 1);
+            band as f64 / (self.bands - 1) as f64
+        };
+        self.shadow + level * (self.lit - self.shadow)
+    }
+}
+
+impl Material for Toon {
+    fn scatter(&self, _r_in: &Ray, _rec: &HitRecord) -> Option<Output> {
+        None
+    }
+
+    fn emitted(&self, rec: &HitRecord) -> Color {
+        self.shade(rec.normal)
+    }
+}
+
+// ink lines drawn where neighboring pixels see different objects or
+// surfaces turning by more than crease_angle degrees
+#[derive(Debug, Copy, Clone)]
+pub struct Outline {
+    pub color: Color,
+    pub crease_angle: f64,
+    // line width in pixels, 0 draws nothing
+    pub thickness: i32,
+}
+
+// constructor functions
+impl Outline {
+    pub fn new(color: Color, crease_angle: f64, thickness: i32) -> Outline {
+        Outline {
+            color,
+            crease_angle,
+            thickness,
+        }
+    }
+}
+
+impl Default for Outline {
+    fn default() -> Self {
+        Outline::new(Color::new(0.0, 0.0, 0.0), 45.0, 1)
+    }
+}
+
+// what the first hit through a pixel saw: the index of the top level
+// world object and its normal, None for the sky
+#[derive(Debug, Copy, Clone)]
+pub struct GSample {
+    pub object: Option<usize>,
+    pub normal: Vec3,
+}
+
+// per pixel geometry of an image, rows counted from the bottom
+pub struct GBuffer {
+    pub width: i32,
+    pub height: i32,
+    samples: Vec<GSample>,
+}
+
+// constructor functions
+impl GBuffer {
+    // one ray through the center of every pixel. cameras with a lens
+    // still jitter these rays across the aperture
+    pub fn new(scene_settings: &SceneSettings) -> GBuffer {
+        let width: i32 = scene_settings.image_settings.image_width;
+        let height: i32 = scene_settings.image_settings.image_height;
+        let mut samples: Vec<GSample> = Vec::with_capacity((width * height) as usize);
+        for j in 0..height {
+            for i in 0..width {
+                let (u, v) = film_coordinates(
+                    &scene_settings.image_settings,
+                    i as f64 + 0.5,
+                    j as f64 + 0.5,
+                );
+                let r: Ray = scene_settings.cam.get_ray(u, v);
+                let hit = if scene_settings.cam.in_frame(u, v) {
+                    first_hit(&scene_settings.world, &r)
+                } else {
+                    None
+                };
+                samples.push(match hit {
+                    Some((object, rec)) => GSample {
+                        object: Some(object),
+                        normal: rec.normal,
+                    },
+                    None => GSample {
+                        object: None,
+                        normal: Vec3::new(0.0, 0.0, 0.0),
+                    },
+                });
+            }
+        }
+        GBuffer {
+            width,
+            height,
+            samples,
+        }
+    }
+}
+
+// member functions
+impl GBuffer {
+    pub fn get(&self, i: i32, j: i32) -> GSample {
+        self.samples[(j * self.width + i) as usize]
+    }
+
+    fn discontinuous(a: GSample, b: GSample, min_cos: f64) -> bool {
+        match (a.object, b.object) {
+            (Some(x), Some(y)) => x != y || dot(&a.normal, &b.normal) < min_cos,
+            (None, None) => false,
+            _ => true,
+        }
+    }
+
+    // whether each pixel is covered by an outline, indexed like get
+    pub fn edges(&self, outline: &Outline) -> Vec<bool> {
+        let min_cos: f64 = degrees_to_radians(outline.crease_angle).cos();
+
+        // a pixel differing from its right or upper neighbor starts a line,
+        // so lines are a single pixel wide
+        let mut seeds: Vec<bool> = vec![false; self.samples.len()];
+        for j in 0..self.height {
+            for i in 0..self.width {
+                let s = self.get(i, j);
+                let right =
+                    i + 1 < self.width && GBuffer::discontinuous(s, self.get(i + 1, j), min_cos);
+                let up =
+                    j + 1 < self.height && GBuffer::discontinuous(s, self.get(i, j + 1), min_cos);
+                seeds[(j * self.width + i) as usize] = right || up;
+            }
+        }
+
+        // grown to the requested thickness
+        let reach: i32 = outline.thickness - 1;
+        if reach < 0 {
+            return vec![false; self.samples.len()];
+        }
+        let mut edges: Vec<bool> = vec![false; self.samples.len()];
+        for j in 0..self.height {
+            for i in 0..self.width {
+                edges[(j * self.width + i) as usize] = (-reach..=reach).any(|dj| {
+                    (-reach..=reach).any(|di| {
+                        let (x, y) = (i + di, j + dj);
+                        x >= 0
+                            && y >= 0
+                            && x < self.width
+                            && y < self.height
+                            && seeds[(y * self.width + x) as usize]
+                    })
+                });
+            }
+        }
+        edges
+    }
+}
+
+// closest hit among the world's top level objects, with the index of the
+// object hit
+pub fn first_hit(world: &HittableList, r: &Ray) -> Option<(usize, HitRecord)> {
+    let mut closest: Option<(usize, HitRecord)> = None;
+    let mut closest_so_far: f64 = INFINITY;
+    for (idx, object) in world.objects.iter().enumerate() {
+        if let Some(rec) = object.hit(r, 0.001, closest_so_far) {
+            closest_so_far = rec.t;
+            closest = Some((idx, rec));
+        }
+    }
+    closest
+}
+
+// renders the scene as usual, then draws the outlines over it. the whole
+// image is kept in memory since lines need neighboring pixels
+pub fn render_outlined(scene_settings: SceneSettings, outline: Outline, num_threads: usize) {
+    let width: i32 = scene_settings.image_settings.image_width;
+    let height: i32 = scene_settings.image_settings.image_height;
+    let samples_per_pixel: i32 = scene_settings.image_settings.samples_per_pixel;
+
+    // rows in output order, top to bottom
+    let rows: Vec<i32> = (0..height).rev().collect();
+    let rows_per_thread: usize = rows.len().div_ceil(num_threads.max(1)).max(1);
+    let scene: &SceneSettings = &scene_settings;
+    let colors: Vec<Color> = thread::scope(|s| {
+        let handles: Vec<_> = rows
+            .chunks(rows_per_thread)
+            .map(|rows| {
+                s.spawn(move || {
+                    let mut v: Vec<Color> = vec![];
+                    for &j in rows {
+                        eprintln!("running line {j}");
+                        for i in 0..width {
+                            v.push(sample_pixel(scene, i, j));
+                        }
+                    }
+                    v
+                })
+            })
+            .collect();
+        handles
+            .into_iter()
+            .flat_map(|h| h.join().unwrap())
+            .collect()
+    });
+
+    let edges: Vec<bool> = GBuffer::new(&scene_settings).edges(&outline);
+    for (k, color) in colors.into_iter().enumerate() {
+        let (i, j) = (k as i32 % width, height - 1 - k as i32 / width);
+        if edges[(j * width + i) as usize] {
+            // colors are sums over the samples
+            write_color(samples_per_pixel as f64 * outline.color, samples_per_pixel);
+        } else {
+            write_color(color, samples_per_pixel);
+        }
+    }
+}
@@ -0,0 +1,51 @@
+use crate::vec3::*;
+
+// orthonormal basis, with w aligned to a given direction
+#[derive(Debug, Copy, Clone)]
+pub struct Onb {
+    pub axis: [Vec3; 3],
+}
+
+// constructor functions
+impl Onb {
+    pub fn build_from_w(n: Vec3) -> Onb {
+        let w = unit_vector(n);
+        let a = if w.x().abs() > 0.9 {
+            Vec3::new(0.0, 1.0, 0.0)
+        } else {
+            Vec3::new(1.0, 0.0, 0.0)
+        };
+        let v = unit_vector(cross(&w, &a));
+        let u = cross(&w, &v);
+        Onb { axis: [u, v, w] }
+    }
+}
+
+// member access functions
+impl Onb {
+    pub fn u(&self) -> Vec3 {
+        self.axis[0]
+    }
+
+    pub fn v(&self) -> Vec3 {
+        self.axis[1]
+    }
+
+    pub fn w(&self) -> Vec3 {
+        self.axis[2]
+    }
+
+    // converts local basis coordinates into a world vector
+    pub fn local(&self, a: f64, b: f64, c: f64) -> Vec3 {
+        a * self.u() + b * self.v() + c * self.w()
+    }
+
+    pub fn local_vec(&self, a: Vec3) -> Vec3 {
+        self.local(a.x(), a.y(), a.z())
+    }
+
+    // converts a world vector into local basis coordinates
+    pub fn to_local(&self, a: Vec3) -> Vec3 {
+        Vec3::new(dot(&a, &self.u()), dot(&a, &self.v()), dot(&a, &self.w()))
+    }
+}
@@ -0,0 +1,175 @@
+use crate::camera::Camera;
+use crate::ray::Ray;
+use crate::rtweekend::{degrees_to_radians, PI};
+use crate::vec3::*;
+
+// width over height of a cube map laid out as a strip of six faces
+pub const CUBE_MAP_ASPECT_RATIO: f64 = 6.0;
+
+// camera basis looking from lookfrom towards lookat, as in
+// PerspectiveCamera: u right, v up and w backwards
+pub(crate) fn view_basis(lookfrom: Point3, lookat: Point3, vup: Vec3) -> (Vec3, Vec3, Vec3) {
+    let w = unit_vector(lookfrom - lookat);
+    let u = unit_vector(cross(&vup, &w));
+    let v = cross(&w, &u);
+    (u, v, w)
+}
+
+// longitude and latitude seen at film position (s, t) of a 2:1
+// panorama, both zero at the center of the image
+pub(crate) fn equirectangular_angles(s: f64, t: f64) -> (f64, f64) {
+    ((s - 0.5) * 2.0 * PI, (t - 0.5) * PI)
+}
+
+// direction at a longitude and latitude in the view basis u, v, w
+pub(crate) fn equirectangular_direction(
+    longitude: f64,
+    latitude: f64,
+    u: Vec3,
+    v: Vec3,
+    w: Vec3,
+) -> Vec3 {
+    latitude.cos() * longitude.sin() * u + latitude.sin() * v - latitude.cos() * longitude.cos() * w
+}
+
+// full 360 by 180 degree latitude-longitude panorama, meant for a 2:1
+// image. the center of the image looks at lookat
+pub struct EquirectangularCamera {
+    origin: Point3,
+    u: Vec3,
+    v: Vec3,
+    w: Vec3,
+}
+
+impl EquirectangularCamera {
+    pub fn new(lookfrom: Point3, lookat: Point3, vup: Vec3) -> EquirectangularCamera {
+        let (u, v, w) = view_basis(lookfrom, lookat, vup);
+        EquirectangularCamera {
+            origin: lookfrom,
+            u,
+            v,
+            w,
+        }
+    }
+}
+
+impl Camera for EquirectangularCamera {
+    fn get_ray(&self, s: f64, t: f64) -> Ray {
+        let (longitude, latitude) = equirectangular_angles(s, t);
+        let direction: Vec3 =
+            equirectangular_direction(longitude, latitude, self.u, self.v, self.w);
+        Ray::new(self.origin, direction)
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum FisheyeProjection {
+    // distance from the image center proportional to the angle off axis
+    Equidistant,
+    // preserves solid angle, like most real fisheye lenses
+    Equisolid,
+}
+
+// circular fisheye, the image circle fitting the height of the image.
+// fov is the full field of view in degrees, up to 360
+pub struct FisheyeCamera {
+    origin: Point3,
+    u: Vec3,
+    v: Vec3,
+    w: Vec3,
+    fov: f64,
+    aspect_ratio: f64,
+    pub projection: FisheyeProjection,
+}
+
+impl FisheyeCamera {
+    pub fn new(
+        lookfrom: Point3,
+        lookat: Point3,
+        vup: Vec3,
+        fov: f64,
+        aspect_ratio: f64,
+        projection: FisheyeProjection,
+    ) -> FisheyeCamera {
+        let (u, v, w) = view_basis(lookfrom, lookat, vup);
+        FisheyeCamera {
+            origin: lookfrom,
+            u,
+            v,
+            w,
+            fov: fov.clamp(1e-3, 360.0),
+            aspect_ratio,
+            projection,
+        }
+    }
+
+    // position on the film relative to the center, the image circle
+    // having radius one
+    fn film(&self, s: f64, t: f64) -> (f64, f64) {
+        ((2.0 * s - 1.0) * self.aspect_ratio, 2.0 * t - 1.0)
+    }
+
+    // angle off the optical axis for a distance r from the image center
+    pub fn theta(&self, r: f64) -> f64 {
+        let theta_max: f64 = degrees_to_radians(self.fov) / 2.0;
+        match self.projection {
+            FisheyeProjection::Equidistant => r * theta_max,
+            FisheyeProjection::Equisolid => {
+                2.0 * (r * (theta_max / 2.0).sin()).clamp(-1.0, 1.0).asin()
+            }
+        }
+    }
+}
+
+impl Camera for FisheyeCamera {
+    fn get_ray(&self, s: f64, t: f64) -> Ray {
+        let (x, y) = self.film(s, t);
+        let r: f64 = (x * x + y * y).sqrt();
+        let theta: f64 = self.theta(r);
+        let (cos_phi, sin_phi) = if r > 0.0 { (x / r, y / r) } else { (1.0, 0.0) };
+        let direction: Vec3 =
+            theta.sin() * (cos_phi * self.u + sin_phi * self.v) - theta.cos() * self.w;
+        Ray::new(self.origin, direction)
+    }
+
+    fn in_frame(&self, s: f64, t: f64) -> bool {
+        let (x, y) = self.film(s, t);
+        x * x + y * y <= 1.0
+    }
+}
+
+// six 90 degree faces in a strip, ordered +x, -x, +y, -y, +z, -z along
+// the world axes with the usual cube map orientations, for environment
+// baking. meant for an image of CUBE_MAP_ASPECT_RATIO
+pub struct CubeMapCamera {
+    origin: Point3,
+}
+
+impl CubeMapCamera {
+    pub fn new(origin: Point3) -> CubeMapCamera {
+        CubeMapCamera { origin }
+    }
+
+    // direction through position (a, b) in [-1, 1] on a face, a to the
+    // right and b up
+    pub fn face_direction(face: usize, a: f64, b: f64) -> Vec3 {
+        match face {
+            0 => Vec3::new(1.0, b, -a),
+            1 => Vec3::new(-1.0, b, a),
+            2 => Vec3::new(a, 1.0, -b),
+            3 => Vec3::new(a, -1.0, b),
+            4 => Vec3::new(a, b, 1.0),
+            _ => Vec3::new(-a, b, -1.0),
+        }
+    }
+}
+
+impl Camera for CubeMapCamera {
+    fn get_ray(&self, s: f64, t: f64) -> Ray {
+        let x: f64 = s.clamp(0.0, 1.0) * 6.0;
+        let face: usize = (x as usize).min(5);
+        let a: f64 = 2.0 * (x - face as f64) - 1.0;
+        let b: f64 = 2.0 * t - 1.0;
+        Ray::new(self.origin, CubeMapCamera::face_direction(face, a, b))
+    }
+}
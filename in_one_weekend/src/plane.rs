@@ -0,0 +1,64 @@
+use crate::aabb::Aabb;
+use crate::hittable::{HitRecord, Hittable};
+use crate::material::Material;
+use crate::onb::Onb;
+use crate::ray::Ray;
+use crate::vec3::*;
+
+use std::sync::Arc;
+
+// infinite plane through `point`, facing along `normal`
+pub struct Plane {
+    pub point: Point3,
+    pub normal: Vec3,
+    pub material: Arc<dyn Material + Sync + Send>,
+    basis: Onb,
+}
+
+// constructor and setter functions
+impl Plane {
+    pub fn new(point: Point3, normal: Vec3, material: Arc<dyn Material + Sync + Send>) -> Plane {
+        let basis = Onb::build_from_w(normal);
+        Plane {
+            point,
+            normal: basis.w(),
+            material,
+            basis,
+        }
+    }
+}
+
+impl Hittable for Plane {
+    fn hit(&self, r: &Ray, t_min: f64, t_max: f64) -> Option<HitRecord> {
+        let denom = dot(&self.normal, &r.direction());
+        if denom.abs() < 1e-12 {
+            return None;
+        }
+        let t = dot(&(self.point - r.origin()), &self.normal) / denom;
+        if t < t_min || t_max < t {
+            return None;
+        }
+
+        // uv tiles once per unit of distance along the plane
+        let p = r.at(t);
+        let local = self.basis.to_local(p - self.point);
+        let mut hr = HitRecord {
+            t,
+            p,
+            normal: Vec3::new(0.0, 0.0, 0.0),
+            u: local.x() - local.x().floor(),
+            v: local.y() - local.y().floor(),
+            dpdu: Vec3::new(0.0, 0.0, 0.0),
+            dpdv: Vec3::new(0.0, 0.0, 0.0),
+            front_face: false,
+            material: Arc::clone(&self.material),
+        };
+        hr.set_face_normal(r, self.normal);
+        (hr.dpdu, hr.dpdv) = (self.basis.u(), self.basis.v());
+        Some(hr)
+    }
+
+    fn bounding_box(&self) -> Option<Aabb> {
+        None
+    }
+}
@@ -0,0 +1,140 @@
+use crate::rtweekend::PI;
+
+// closed form real roots of low degree polynomials, after Schwarze's
+// "Cubic and Quartic Roots" (Graphics Gems I). coefficients are given
+// from the highest degree term down, roots are returned unsorted.
+
+const EQN_EPS: f64 = 1e-9;
+
+fn is_zero(x: f64) -> bool {
+    x.abs() < EQN_EPS
+}
+
+// a x^2 + b x + c = 0
+pub fn solve_quadratic(a: f64, b: f64, c: f64) -> Vec<f64> {
+    if a == 0.0 {
+        return if b == 0.0 { vec![] } else { vec![-c / b] };
+    }
+    // normal form: x^2 + 2px + q = 0
+    let p = b / (2.0 * a);
+    let q = c / a;
+    let discriminant = p * p - q;
+
+    if is_zero(discriminant) {
+        vec![-p]
+    } else if discriminant < 0.0 {
+        vec![]
+    } else {
+        let sqrt_d = discriminant.sqrt();
+        vec![sqrt_d - p, -sqrt_d - p]
+    }
+}
+
+// a x^3 + b x^2 + c x + d = 0
+pub fn solve_cubic(a: f64, b: f64, c: f64, d: f64) -> Vec<f64> {
+    if a == 0.0 {
+        return solve_quadratic(b, c, d);
+    }
+    // normal form: x^3 + Ax^2 + Bx + C = 0
+    let big_a = b / a;
+    let big_b = c / a;
+    let big_c = d / a;
+
+    // substitute x = y - A/3 to eliminate the quadric term: y^3 + 3py + 2q = 0
+    let sq_a = big_a * big_a;
+    let p = (-sq_a / 3.0 + big_b) / 3.0;
+    let q = (2.0 / 27.0 * big_a * sq_a - big_a * big_b / 3.0 + big_c) / 2.0;
+    let cb_p = p * p * p;
+    let discriminant = q * q + cb_p;
+
+    let roots = if is_zero(discriminant) {
+        if is_zero(q) {
+            vec![0.0]
+        } else {
+            let u = (-q).cbrt();
+            vec![2.0 * u, -u]
+        }
+    } else if discriminant < 0.0 {
+        // three real roots
+        let phi = (-q / (-cb_p).sqrt()).clamp(-1.0, 1.0).acos() / 3.0;
+        let t = 2.0 * (-p).sqrt();
+        vec![
+            t * phi.cos(),
+            -t * (phi + PI / 3.0).cos(),
+            -t * (phi - PI / 3.0).cos(),
+        ]
+    } else {
+        // one real root
+        let sqrt_d = discriminant.sqrt();
+        vec![(sqrt_d - q).cbrt() - (sqrt_d + q).cbrt()]
+    };
+
+    let sub = big_a / 3.0;
+    roots.into_iter().map(|y| y - sub).collect()
+}
+
+// a x^4 + b x^3 + c x^2 + d x + e = 0
+// roots are refined with a few newton steps, since ferrari's method
+// loses precision when the roots are far from the origin
+pub fn solve_quartic(a: f64, b: f64, c: f64, d: f64, e: f64) -> Vec<f64> {
+    if a == 0.0 {
+        return solve_cubic(b, c, d, e);
+    }
+    // normal form: x^4 + Ax^3 + Bx^2 + Cx + D = 0
+    let big_a = b / a;
+    let big_b = c / a;
+    let big_c = d / a;
+    let big_d = e / a;
+
+    // substitute x = y - A/4 to eliminate the cubic term: y^4 + py^2 + qy + r = 0
+    let sq_a = big_a * big_a;
+    let p = -3.0 / 8.0 * sq_a + big_b;
+    let q = sq_a * big_a / 8.0 - big_a * big_b / 2.0 + big_c;
+    let r = -3.0 / 256.0 * sq_a * sq_a + sq_a * big_b / 16.0 - big_a * big_c / 4.0 + big_d;
+
+    let mut roots: Vec<f64> = if is_zero(r) {
+        // no absolute term: y(y^3 + py + q) = 0
+        let mut s = solve_cubic(1.0, 0.0, p, q);
+        s.push(0.0);
+        s
+    } else {
+        // solve the resolvent cubic and take one real root
+        let z = solve_cubic(1.0, -p / 2.0, -r, r * p / 2.0 - q * q / 8.0)[0];
+
+        // build two quadratic equations from it
+        let mut u = z * z - r;
+        let mut v = 2.0 * z - p;
+        if is_zero(u) {
+            u = 0.0;
+        } else if u > 0.0 {
+            u = u.sqrt();
+        } else {
+            return vec![];
+        }
+        if is_zero(v) {
+            v = 0.0;
+        } else if v > 0.0 {
+            v = v.sqrt();
+        } else {
+            return vec![];
+        }
+
+        let v = if q < 0.0 { -v } else { v };
+        let mut s = solve_quadratic(1.0, v, z - u);
+        s.extend(solve_quadratic(1.0, -v, z + u));
+        s
+    };
+
+    let sub = big_a / 4.0;
+    for x in roots.iter_mut() {
+        *x -= sub;
+        for _ in 0..2 {
+            let f = (((a * *x + b) * *x + c) * *x + d) * *x + e;
+            let df = ((4.0 * a * *x + 3.0 * b) * *x + 2.0 * c) * *x + d;
+            if df != 0.0 {
+                *x -= f / df;
+            }
+        }
+    }
+    roots
+}
@@ -0,0 +1,294 @@
+use crate::color::luminance;
+use crate::fresnel::{fresnel_dielectric, fresnel_schlick};
+use crate::hittable::HitRecord;
+use crate::material::{sample_cosine_local, Material, Output};
+use crate::microfacet::TrowbridgeReitz;
+use crate::onb::Onb;
+use crate::ray::Ray;
+use crate::rtweekend::{random_f64, PI};
+use crate::vec3::*;
+
+// disney style principled bsdf: one material whose sliders, all in [0, 1]
+// except ior, blend between diffuse, metal, glass and coated looks.
+// each scatter picks one lobe at random and divides its weight by the
+// probability of picking it
+pub struct Principled {
+    pub base_color: Color,
+    pub metallic: f64,
+    pub roughness: f64,
+    // dielectric reflectance, 0.5 is a typical 4%
+    pub specular: f64,
+    // tints dielectric reflections towards the base color
+    pub specular_tint: f64,
+    // grazing retro reflection for cloth
+    pub sheen: f64,
+    pub sheen_tint: f64,
+    // second, white and usually glossy specular layer
+    pub clearcoat: f64,
+    pub clearcoat_gloss: f64,
+    pub transmission: f64,
+    pub ior: f64,
+}
+
+impl Default for Principled {
+    fn default() -> Self {
+        Principled {
+            base_color: Color::new(0.8, 0.8, 0.8),
+            metallic: 0.0,
+            roughness: 0.5,
+            specular: 0.5,
+            specular_tint: 0.0,
+            sheen: 0.0,
+            sheen_tint: 0.5,
+            clearcoat: 0.0,
+            clearcoat_gloss: 1.0,
+            transmission: 0.0,
+            ior: 1.5,
+        }
+    }
+}
+
+// constructor functions, set the other sliders with struct update syntax:
+// `Principled { metallic: 1.0, ..Principled::new(base_color) }`
+impl Principled {
+    pub fn new(base_color: Color) -> Principled {
+        Principled {
+            base_color,
+            ..Default::default()
+        }
+    }
+}
+
+fn lerp(a: f64, b: f64, t: f64) -> f64 {
+    (1.0 - t) * a + t * b
+}
+
+fn lerp_color(a: Color, b: Color, t: f64) -> Color {
+    (1.0 - t) * a + t * b
+}
+
+fn schlick_weight(cos_theta: f64) -> f64 {
+    (1.0 - cos_theta.clamp(0.0, 1.0)).powi(5)
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Lobe {
+    Diffuse,
+    Specular,
+    Transmission,
+    Clearcoat,
+}
+
+// member functions
+impl Principled {
+    // base color normalized to unit luminance, for the tint sliders
+    fn tint(&self) -> Color {
+        let lum = luminance(self.base_color);
+        if lum > 0.0 {
+            self.base_color / lum
+        } else {
+            Color::new(1.0, 1.0, 1.0)
+        }
+    }
+
+    fn diffuse_weight(&self) -> f64 {
+        (1.0 - self.metallic) * (1.0 - self.transmission)
+    }
+
+    fn transmission_weight(&self) -> f64 {
+        (1.0 - self.metallic) * self.transmission
+    }
+
+    // probability of sampling each lobe
+    pub fn lobe_probabilities(&self) -> [(Lobe, f64); 4] {
+        let weights = [
+            (Lobe::Diffuse, self.diffuse_weight()),
+            (Lobe::Specular, 1.0),
+            (Lobe::Transmission, self.transmission_weight()),
+            (Lobe::Clearcoat, 0.25 * self.clearcoat),
+        ];
+        let total: f64 = weights.iter().map(|w| w.1).sum();
+        weights.map(|(lobe, w)| (lobe, w / total))
+    }
+
+    // tinted reflectance of the dielectric base
+    fn dielectric_fresnel(&self, cos_theta: f64) -> Color {
+        let white = Color::new(1.0, 1.0, 1.0);
+        let f0 = 0.08 * self.specular * lerp_color(white, self.tint(), self.specular_tint);
+        Color::new(
+            fresnel_schlick(cos_theta, f0.x()),
+            fresnel_schlick(cos_theta, f0.y()),
+            fresnel_schlick(cos_theta, f0.z()),
+        )
+    }
+
+    // reflectance of the specular lobe, blending tinted dielectric,
+    // glass and metal fresnel by the metallic and transmission sliders
+    fn specular_fresnel(&self, cos_theta: f64) -> Color {
+        let metal = Color::new(
+            fresnel_schlick(cos_theta, self.base_color.x()),
+            fresnel_schlick(cos_theta, self.base_color.y()),
+            fresnel_schlick(cos_theta, self.base_color.z()),
+        );
+        let glass = fresnel_dielectric(cos_theta, self.ior);
+        self.metallic * metal
+            + self.diffuse_weight() * self.dielectric_fresnel(cos_theta)
+            + self.transmission_weight() * Color::new(glass, glass, glass)
+    }
+
+    // the eval functions give each lobe's sample weight, f * cos / pdf,
+    // for a direction sampled by that lobe: wi for the diffuse lobe and
+    // the microfacet normal wm for the others
+
+    // diffuse with disney's roughness dependent retro reflection, plus
+    // sheen. it only gets the light the dielectric coating lets through
+    pub fn eval_diffuse(&self, wo: Vec3, wi: Vec3) -> Color {
+        if wi.z() <= 0.0 {
+            return Color::new(0.0, 0.0, 0.0);
+        }
+        let wh = unit_vector(wi + wo);
+        let cos_theta_d = dot(&wi, &wh);
+
+        let fl = schlick_weight(wi.z());
+        let fv = schlick_weight(wo.z());
+        let fd90 = 0.5 + 2.0 * self.roughness * cos_theta_d * cos_theta_d;
+        let retro = lerp(1.0, fd90, fl) * lerp(1.0, fd90, fv);
+        let white = Color::new(1.0, 1.0, 1.0);
+        let transmitted = white - self.dielectric_fresnel(wo.z());
+
+        let sheen_color = lerp_color(white, self.tint(), self.sheen_tint);
+        let sheen = PI * self.sheen * schlick_weight(cos_theta_d) * sheen_color;
+        self.diffuse_weight() * (retro * transmitted * self.base_color + sheen)
+    }
+
+    pub fn eval_specular(&self, wo: Vec3, wm: Vec3) -> Color {
+        let distribution = self.distribution();
+        let wi = reflect(-wo, wm);
+        if wi.z() <= 0.0 {
+            return Color::new(0.0, 0.0, 0.0);
+        }
+        let weight = distribution.g(wo, wi) / distribution.g1(wo);
+        weight * self.specular_fresnel(dot(&wo, &wm))
+    }
+
+    pub fn eval_transmission(&self, wo: Vec3, wm: Vec3) -> Color {
+        let distribution = self.distribution();
+        let fresnel = fresnel_dielectric(dot(&wo, &wm), self.ior);
+        let wi = refract(-wo, wm, 1.0 / self.ior);
+        if fresnel >= 1.0 || wi.z() >= 0.0 {
+            return Color::new(0.0, 0.0, 0.0);
+        }
+        let weight = distribution.g(wo, wi) / distribution.g1(wo);
+        self.transmission_weight() * (1.0 - fresnel) * weight * self.base_color
+    }
+
+    pub fn eval_clearcoat(&self, wo: Vec3, wm: Vec3) -> Color {
+        let distribution = self.clearcoat_distribution();
+        let wi = reflect(-wo, wm);
+        if wi.z() <= 0.0 {
+            return Color::new(0.0, 0.0, 0.0);
+        }
+        let weight =
+            0.25 * self.clearcoat * fresnel_schlick(dot(&wo, &wm), 0.04) * distribution.g(wo, wi)
+                / distribution.g1(wo);
+        Color::new(weight, weight, weight)
+    }
+
+    fn clearcoat_distribution(&self) -> TrowbridgeReitz {
+        let alpha = lerp(0.1, 0.001, self.clearcoat_gloss);
+        TrowbridgeReitz::new(alpha, alpha)
+    }
+
+    fn distribution(&self) -> TrowbridgeReitz {
+        TrowbridgeReitz::from_roughness(self.roughness)
+    }
+
+    // picks a direction with the given lobe, along with its weight
+    fn sample_lobe(&self, lobe: Lobe, wo: Vec3) -> Option<(Vec3, Color)> {
+        let (wi, weight) = match lobe {
+            Lobe::Diffuse => {
+                let wi = sample_cosine_local();
+                (wi, self.eval_diffuse(wo, wi))
+            }
+            Lobe::Specular => {
+                let wm = self
+                    .distribution()
+                    .sample_wm(wo, random_f64(), random_f64());
+                (reflect(-wo, wm), self.eval_specular(wo, wm))
+            }
+            Lobe::Transmission => {
+                let wm = self
+                    .distribution()
+                    .sample_wm(wo, random_f64(), random_f64());
+                (
+                    refract(-wo, wm, 1.0 / self.ior),
+                    self.eval_transmission(wo, wm),
+                )
+            }
+            Lobe::Clearcoat => {
+                let distribution = self.clearcoat_distribution();
+                let wm = distribution.sample_wm(wo, random_f64(), random_f64());
+                (reflect(-wo, wm), self.eval_clearcoat(wo, wm))
+            }
+        };
+        // directions on the wrong side of the surface weigh nothing
+        if weight.near_zero() {
+            return None;
+        }
+        Some((wi, weight))
+    }
+
+    // leaving a transmissive object only the rough glass interface applies
+    fn sample_interior(&self, wo: Vec3) -> Option<(Vec3, Color)> {
+        let distribution = self.distribution();
+        let wm = distribution.sample_wm(wo, random_f64(), random_f64());
+        let eta = 1.0 / self.ior;
+        let wi = if random_f64() < fresnel_dielectric(dot(&wo, &wm), eta) {
+            let wi = reflect(-wo, wm);
+            if wi.z() <= 0.0 {
+                return None;
+            }
+            wi
+        } else {
+            let wi = refract(-wo, wm, self.ior);
+            if wi.z() >= 0.0 {
+                return None;
+            }
+            wi
+        };
+        let weight = distribution.g(wo, wi) / distribution.g1(wo);
+        Some((wi, Color::new(weight, weight, weight)))
+    }
+}
+
+impl Material for Principled {
+    fn scatter(&self, r_in: &Ray, rec: &HitRecord) -> Option<Output> {
+        let uvw = Onb::build_from_w(rec.normal);
+        let wo = uvw.to_local(-unit_vector(r_in.direction()));
+        if wo.z() <= 0.0 {
+            return None;
+        }
+
+        let sample = if !rec.front_face && self.transmission_weight() > 0.0 {
+            self.sample_interior(wo)
+        } else {
+            let probabilities = self.lobe_probabilities();
+            let mut xi = random_f64();
+            let (lobe, p) = *probabilities
+                .iter()
+                .find(|(_, p)| {
+                    xi -= p;
+                    xi < 0.0
+                })
+                .unwrap_or(&probabilities[1]);
+            self.sample_lobe(lobe, wo)
+                .map(|(wi, weight)| (wi, weight / p))
+        };
+
+        let (wi, attenuation) = sample?;
+        Some(Output {
+            attenuation,
+            scattered: Ray::new(rec.p, uvw.local_vec(wi)),
+        })
+    }
+}
@@ -0,0 +1,82 @@
+use crate::aabb::Aabb;
+use crate::hittable::{HitRecord, Hittable};
+use crate::material::Material;
+use crate::ray::Ray;
+use crate::vec3::*;
+
+use std::sync::Arc;
+
+// parallelogram with corner `q` spanned by edges `u` and `v`, uvs run
+// from 0 to 1 along each edge
+pub struct Quad {
+    pub q: Point3,
+    pub u: Vec3,
+    pub v: Vec3,
+    pub material: Arc<dyn Material + Sync + Send>,
+    normal: Vec3,
+    // maps a point in the quad's plane to its (u, v) coordinates
+    w: Vec3,
+}
+
+// constructor functions
+impl Quad {
+    pub fn new(q: Point3, u: Vec3, v: Vec3, material: Arc<dyn Material + Sync + Send>) -> Quad {
+        let n = cross(&u, &v);
+        Quad {
+            q,
+            u,
+            v,
+            material,
+            normal: unit_vector(n),
+            w: n / dot(&n, &n),
+        }
+    }
+}
+
+impl Hittable for Quad {
+    fn hit(&self, r: &Ray, t_min: f64, t_max: f64) -> Option<HitRecord> {
+        let denom = dot(&self.normal, &r.direction());
+        if denom.abs() < 1e-12 {
+            return None;
+        }
+        let t = dot(&(self.q - r.origin()), &self.normal) / denom;
+        if t < t_min || t_max < t {
+            return None;
+        }
+
+        let p = r.at(t);
+        let planar = p - self.q;
+        let a = dot(&self.w, &cross(&planar, &self.v));
+        let b = dot(&self.w, &cross(&self.u, &planar));
+        if !(0.0..=1.0).contains(&a) || !(0.0..=1.0).contains(&b) {
+            return None;
+        }
+
+        let mut hr = HitRecord {
+            t,
+            p,
+            normal: Vec3::new(0.0, 0.0, 0.0),
+            u: a,
+            v: b,
+            dpdu: self.u,
+            dpdv: self.v,
+            front_face: false,
+            material: Arc::clone(&self.material),
+        };
+        hr.set_face_normal(r, self.normal);
+        Some(hr)
+    }
+
+    fn bounding_box(&self) -> Option<Aabb> {
+        // padded so axis aligned quads still have volume
+        let pad = Vec3::new(1e-4, 1e-4, 1e-4);
+        let corners = [
+            self.q,
+            self.q + self.u,
+            self.q + self.v,
+            self.q + self.u + self.v,
+        ];
+        let b = Aabb::from_points(&corners);
+        Some(Aabb::new(b.min() - pad, b.max() + pad))
+    }
+}
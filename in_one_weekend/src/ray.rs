@@ -4,12 +4,18 @@ use crate::vec3::{Point3, Vec3};
 pub struct Ray {
     pub orig: Point3,
     pub dir: Vec3,
+    // hero wavelength in nanometers when rendering spectrally
+    pub wavelength: Option<f64>,
 }
 
 // constructors & setters
 impl Ray {
     pub fn new(o: Point3, d: Vec3) -> Ray {
-        Ray { orig: o, dir: d }
+        Ray {
+            orig: o,
+            dir: d,
+            wavelength: None,
+        }
     }
 }
 
@@ -1,8 +1,8 @@
 use rand::Rng;
 
 // constants
-pub const INFINITY: f64 = std::f64::INFINITY;
-pub const PI: f64 = 3.1415926535897932385;
+pub const INFINITY: f64 = f64::INFINITY;
+pub const PI: f64 = std::f64::consts::PI;
 
 // utility functions
 pub fn degrees_to_radians(degrees: f64) -> f64 {
@@ -1,9 +1,16 @@
+use crate::aabb::Aabb;
 use crate::camera::*;
+use crate::constant_medium::ConstantMedium;
+use crate::csg::Csg;
+use crate::density_grid::DensityGrid;
+use crate::grid_medium::GridMedium;
 use crate::hittable::Hittable;
 use crate::hittable_list::*;
 use crate::material::*;
+use crate::npr::Toon;
 use crate::ray::Ray;
 use crate::rtweekend::*;
+use crate::spectral::{rgb_to_spectrum, Dispersion, SampledSpectrum, SampledWavelengths};
 use crate::sphere::*;
 use crate::vec3::*;
 
@@ -15,21 +22,66 @@ pub fn ray_color(r: &Ray, world: &HittableList, depth: i32) -> Color {
     }
 
     if let Some(rec) = world.hit(r, 0.001, INFINITY) {
+        let emitted: Color = rec.material.emitted(&rec);
         if let Some(Output {
             attenuation,
             scattered,
         }) = rec.material.scatter(r, &rec)
         {
-            return attenuation * ray_color(&scattered, world, depth - 1);
+            return emitted + attenuation * ray_color(&scattered, world, depth - 1);
         } else {
-            return Color::new(0.0, 0.0, 0.0);
+            return emitted;
         }
     }
+    sky_color(r)
+}
+
+fn sky_color(r: &Ray) -> Color {
     let unit_dir: Vec3 = unit_vector(r.direction());
     let t = 0.5 * (unit_dir.y() + 1.0);
     (1.0 - t) * Color::new(1.0, 1.0, 1.0) + t * Color::new(0.5, 0.7, 1.0)
 }
 
+// ray_color carrying a set of hero sampled wavelengths along the path,
+// rgb albedos and the sky are upsampled to spectra at every bounce and
+// the result is converted back to rgb through cie xyz
+pub fn ray_color_spectral(r: &Ray, world: &HittableList, depth: i32) -> Color {
+    let mut lambda = SampledWavelengths::sample_uniform(random_f64());
+    let radiance = spectral_radiance(r, world, depth, &mut lambda);
+    lambda.to_rgb(&radiance)
+}
+
+fn spectral_radiance(
+    r: &Ray,
+    world: &HittableList,
+    depth: i32,
+    lambda: &mut SampledWavelengths,
+) -> SampledSpectrum {
+    if depth <= 0 {
+        return SampledSpectrum::constant(0.0);
+    }
+
+    if let Some(rec) = world.hit(r, 0.001, INFINITY) {
+        if rec.material.dispersive() {
+            lambda.terminate_secondary();
+        }
+        let mut r_in: Ray = *r;
+        r_in.wavelength = Some(lambda.hero());
+        let emitted = rgb_to_spectrum(rec.material.emitted(&rec), lambda);
+        if let Some(Output {
+            attenuation,
+            scattered,
+        }) = rec.material.scatter(&r_in, &rec)
+        {
+            let incoming = spectral_radiance(&scattered, world, depth - 1, lambda);
+            return emitted + rgb_to_spectrum(attenuation, lambda) * incoming;
+        } else {
+            return emitted;
+        }
+    }
+    rgb_to_spectrum(sky_color(r), lambda)
+}
+
 #[derive(Copy, Clone)]
 pub struct ImageSettings {
     pub aspect_ratio: f64,
@@ -37,11 +89,13 @@ pub struct ImageSettings {
     pub image_height: i32,
     pub samples_per_pixel: i32,
     pub max_depth: i32,
+    // trace wavelengths instead of rgb, needed for dispersion
+    pub spectral: bool,
 }
 
 pub struct SceneSettings {
     pub world: HittableList,
-    pub cam: Camera,
+    pub cam: Box<dyn Camera + Sync + Send>,
     pub image_settings: ImageSettings,
 }
 
@@ -115,7 +169,7 @@ pub fn random_scene() -> SceneSettings {
     let aperture: f64 = 0.1;
 
     let aspect_ratio: f64 = 3.0 / 2.0;
-    let cam: Camera = Camera::new(
+    let cam: Box<dyn Camera + Sync + Send> = Box::new(PerspectiveCamera::new(
         lookfrom,
         lookat,
         vup,
@@ -123,16 +177,17 @@ pub fn random_scene() -> SceneSettings {
         aspect_ratio,
         aperture,
         dist_to_focus,
-    );
+    ));
 
     let image_width: i32 = 1200;
 
     let image_settings: ImageSettings = ImageSettings {
-        aspect_ratio: aspect_ratio,
-        image_width: image_width,
+        aspect_ratio,
+        image_width,
         image_height: ((image_width as f64) / aspect_ratio) as i32,
         samples_per_pixel: 500,
         max_depth: 50,
+        spectral: false,
     };
 
     SceneSettings {
@@ -164,16 +219,18 @@ pub fn defocus_blur_scene() -> SceneSettings {
         0.5,
         Arc::clone(&material_center),
     )));
-    world.add(Box::new(Sphere::new(
+    // hollow glass bubble
+    let bubble_outer: Arc<dyn Hittable + Sync + Send> = Arc::new(Sphere::new(
         Point3::new(-1.0, 0.0, -1.0),
         0.5,
         Arc::clone(&material_left),
-    )));
-    world.add(Box::new(Sphere::new(
+    ));
+    let bubble_inner: Arc<dyn Hittable + Sync + Send> = Arc::new(Sphere::new(
         Point3::new(-1.0, 0.0, -1.0),
-        -0.45,
+        0.45,
         Arc::clone(&material_left),
-    )));
+    ));
+    world.add(Box::new(Csg::difference(bubble_outer, bubble_inner)));
     world.add(Box::new(Sphere::new(
         Point3::new(1.0, 0.0, -1.0),
         0.5,
@@ -193,6 +250,7 @@ pub fn defocus_blur_scene() -> SceneSettings {
         image_height,
         samples_per_pixel,
         max_depth,
+        spectral: false,
     };
 
     // camera
@@ -202,7 +260,322 @@ pub fn defocus_blur_scene() -> SceneSettings {
     let dist_to_focus: f64 = (lookfrom - lookat).length();
     let vfov: f64 = 20.0;
     let aperture = 2.0;
-    let cam: Camera = Camera::new(
+    let cam: Box<dyn Camera + Sync + Send> = Box::new(PerspectiveCamera::new(
+        lookfrom,
+        lookat,
+        vup,
+        vfov,
+        aspect_ratio,
+        aperture,
+        dist_to_focus,
+    ));
+
+    SceneSettings {
+        world,
+        cam,
+        image_settings,
+    }
+}
+
+// participating media scene: a smoke blob, a forward scattering glowing
+// haze and thin fog filling the whole world
+#[allow(dead_code)]
+pub fn fog_scene() -> SceneSettings {
+    let mut world: HittableList = HittableList::new();
+
+    let material_ground: Arc<dyn Material + Sync + Send> =
+        Arc::new(Lambertian::new(Color::new(0.5, 0.5, 0.5)));
+    let material_glass: Arc<dyn Material + Sync + Send> = Arc::new(Dielectric::new(1.5));
+
+    world.add(Box::new(Sphere::new(
+        Point3::new(0.0, -1000.0, 0.0),
+        1000.0,
+        Arc::clone(&material_ground),
+    )));
+    world.add(Box::new(Sphere::new(
+        Point3::new(2.0, 1.0, 0.0),
+        1.0,
+        Arc::clone(&material_glass),
+    )));
+
+    // smoke
+    let smoke_boundary: Arc<dyn Hittable + Sync + Send> = Arc::new(Sphere::new(
+        Point3::new(-2.0, 1.0, 0.0),
+        1.0,
+        Arc::clone(&material_glass),
+    ));
+    world.add(Box::new(ConstantMedium::with_color(
+        smoke_boundary,
+        2.0,
+        Color::new(0.2, 0.2, 0.2),
+    )));
+
+    // forward scattering haze
+    let haze_boundary: Arc<dyn Hittable + Sync + Send> = Arc::new(Sphere::new(
+        Point3::new(0.0, 0.8, -2.0),
+        0.8,
+        Arc::clone(&material_glass),
+    ));
+    let haze_phase: Arc<dyn Material + Sync + Send> =
+        Arc::new(HenyeyGreenstein::new(Color::new(0.9, 0.8, 0.7), 0.7));
+    world.add(Box::new(ConstantMedium::new(
+        haze_boundary,
+        1.5,
+        haze_phase,
+    )));
+
+    // fog around the camera and objects
+    let fog_boundary: Arc<dyn Hittable + Sync + Send> = Arc::new(Sphere::new(
+        Point3::new(0.0, 0.0, 0.0),
+        50.0,
+        Arc::clone(&material_glass),
+    ));
+    world.add(Box::new(ConstantMedium::with_color(
+        fog_boundary,
+        0.02,
+        Color::new(1.0, 1.0, 1.0),
+    )));
+
+    // image settings
+    let aspect_ratio: f64 = 16.0 / 9.0;
+    let image_width: i32 = 400;
+    let image_height: i32 = (image_width as f64 / aspect_ratio) as i32;
+    let samples_per_pixel: i32 = 200;
+    let max_depth: i32 = 50;
+
+    let image_settings: ImageSettings = ImageSettings {
+        aspect_ratio,
+        image_width,
+        image_height,
+        samples_per_pixel,
+        max_depth,
+        spectral: false,
+    };
+
+    // camera
+    let lookfrom: Point3 = Point3::new(0.0, 2.0, 10.0);
+    let lookat: Point3 = Point3::new(0.0, 1.0, 0.0);
+    let vup: Vec3 = Vec3::new(0.0, 1.0, 0.0);
+    let dist_to_focus: f64 = (lookfrom - lookat).length();
+    let vfov: f64 = 30.0;
+    let aperture = 0.0;
+    let cam: Box<dyn Camera + Sync + Send> = Box::new(PerspectiveCamera::new(
+        lookfrom,
+        lookat,
+        vup,
+        vfov,
+        aspect_ratio,
+        aperture,
+        dist_to_focus,
+    ));
+
+    SceneSettings {
+        world,
+        cam,
+        image_settings,
+    }
+}
+
+// heterogeneous cloud generated from perlin noise above a ground plane
+#[allow(dead_code)]
+pub fn cloud_scene() -> SceneSettings {
+    let mut world: HittableList = HittableList::new();
+
+    let material_ground: Arc<dyn Material + Sync + Send> =
+        Arc::new(Lambertian::new(Color::new(0.3, 0.5, 0.3)));
+    world.add(Box::new(Sphere::new(
+        Point3::new(0.0, -1000.0, 0.0),
+        1000.0,
+        Arc::clone(&material_ground),
+    )));
+
+    let grid: Arc<DensityGrid> = Arc::new(DensityGrid::from_perlin(64, 4.0, 0.2));
+    let cloud_phase: Arc<dyn Material + Sync + Send> =
+        Arc::new(HenyeyGreenstein::new(Color::new(0.95, 0.95, 0.95), 0.6));
+    world.add(Box::new(GridMedium::new(
+        grid,
+        Aabb::new(Point3::new(-3.0, 1.5, -2.0), Point3::new(3.0, 4.5, 2.0)),
+        8.0,
+        cloud_phase,
+    )));
+
+    // image settings
+    let aspect_ratio: f64 = 16.0 / 9.0;
+    let image_width: i32 = 400;
+    let image_height: i32 = (image_width as f64 / aspect_ratio) as i32;
+    let samples_per_pixel: i32 = 200;
+    let max_depth: i32 = 50;
+
+    let image_settings: ImageSettings = ImageSettings {
+        aspect_ratio,
+        image_width,
+        image_height,
+        samples_per_pixel,
+        max_depth,
+        spectral: false,
+    };
+
+    // camera
+    let lookfrom: Point3 = Point3::new(0.0, 2.0, 12.0);
+    let lookat: Point3 = Point3::new(0.0, 2.5, 0.0);
+    let vup: Vec3 = Vec3::new(0.0, 1.0, 0.0);
+    let dist_to_focus: f64 = (lookfrom - lookat).length();
+    let vfov: f64 = 40.0;
+    let aperture = 0.0;
+    let cam: Box<dyn Camera + Sync + Send> = Box::new(PerspectiveCamera::new(
+        lookfrom,
+        lookat,
+        vup,
+        vfov,
+        aspect_ratio,
+        aperture,
+        dist_to_focus,
+    ));
+
+    SceneSettings {
+        world,
+        cam,
+        image_settings,
+    }
+}
+
+// dense flint spheres that split the sky into rainbow fringes, rendered
+// spectrally
+#[allow(dead_code)]
+pub fn dispersion_scene() -> SceneSettings {
+    let mut world: HittableList = HittableList::new();
+
+    let material_ground: Arc<dyn Material + Sync + Send> =
+        Arc::new(Lambertian::new(Color::new(0.8, 0.8, 0.8)));
+    let material_flint: Arc<dyn Material + Sync + Send> =
+        Arc::new(Dielectric::with_dispersion(Dispersion::dense_flint()));
+    let material_tinted: Arc<dyn Material + Sync + Send> = Arc::new(Dielectric {
+        absorption: Color::new(0.1, 0.6, 1.2),
+        ..Dielectric::with_dispersion(Dispersion::bk7())
+    });
+
+    world.add(Box::new(Sphere::new(
+        Point3::new(0.0, -100.5, -1.0),
+        100.0,
+        Arc::clone(&material_ground),
+    )));
+    world.add(Box::new(Sphere::new(
+        Point3::new(-0.6, 0.0, -1.0),
+        0.5,
+        Arc::clone(&material_flint),
+    )));
+    world.add(Box::new(Sphere::new(
+        Point3::new(0.6, 0.0, -1.0),
+        0.5,
+        Arc::clone(&material_tinted),
+    )));
+
+    // image settings
+    let aspect_ratio: f64 = 16.0 / 9.0;
+    let image_width: i32 = 400;
+    let image_height: i32 = (image_width as f64 / aspect_ratio) as i32;
+    let samples_per_pixel: i32 = 200;
+    let max_depth: i32 = 50;
+
+    let image_settings: ImageSettings = ImageSettings {
+        aspect_ratio,
+        image_width,
+        image_height,
+        samples_per_pixel,
+        max_depth,
+        spectral: true,
+    };
+
+    // camera
+    let lookfrom: Point3 = Point3::new(0.0, 0.5, 2.0);
+    let lookat: Point3 = Point3::new(0.0, 0.0, -1.0);
+    let vup: Vec3 = Vec3::new(0.0, 1.0, 0.0);
+    let dist_to_focus: f64 = (lookfrom - lookat).length();
+    let vfov: f64 = 35.0;
+    let aperture = 0.0;
+    let cam: Box<dyn Camera + Sync + Send> = Box::new(PerspectiveCamera::new(
+        lookfrom,
+        lookat,
+        vup,
+        vfov,
+        aspect_ratio,
+        aperture,
+        dist_to_focus,
+    ));
+
+    SceneSettings {
+        world,
+        cam,
+        image_settings,
+    }
+}
+
+// cel shaded spheres on a toon ground, meant to be rendered with
+// --outline
+#[allow(dead_code)]
+pub fn toon_scene() -> SceneSettings {
+    let mut world: HittableList = HittableList::new();
+
+    let light: Vec3 = Vec3::new(-1.0, 2.0, 1.0);
+    let material_ground: Arc<dyn Material + Sync + Send> = Arc::new(Toon::new(
+        Color::new(0.8, 0.8, 0.6),
+        Color::new(0.4, 0.4, 0.3),
+        light,
+        2,
+    ));
+    let material_red: Arc<dyn Material + Sync + Send> = Arc::new(Toon::new(
+        Color::new(0.9, 0.3, 0.2),
+        Color::new(0.3, 0.05, 0.1),
+        light,
+        3,
+    ));
+    let material_blue: Arc<dyn Material + Sync + Send> = Arc::new(Toon::new(
+        Color::new(0.3, 0.5, 0.9),
+        Color::new(0.05, 0.1, 0.3),
+        light,
+        2,
+    ));
+
+    world.add(Box::new(Sphere::new(
+        Point3::new(0.0, -100.5, -1.0),
+        100.0,
+        Arc::clone(&material_ground),
+    )));
+    world.add(Box::new(Sphere::new(
+        Point3::new(-0.6, 0.0, -1.0),
+        0.5,
+        Arc::clone(&material_red),
+    )));
+    world.add(Box::new(Sphere::new(
+        Point3::new(0.6, 0.0, -1.0),
+        0.5,
+        Arc::clone(&material_blue),
+    )));
+
+    // image settings
+    let aspect_ratio: f64 = 16.0 / 9.0;
+    let image_width: i32 = 400;
+    let image_height: i32 = (image_width as f64 / aspect_ratio) as i32;
+    let samples_per_pixel: i32 = 16;
+    let max_depth: i32 = 50;
+
+    let image_settings: ImageSettings = ImageSettings {
+        aspect_ratio,
+        image_width,
+        image_height,
+        samples_per_pixel,
+        max_depth,
+        spectral: false,
+    };
+
+    // camera
+    let lookfrom: Point3 = Point3::new(0.0, 0.5, 2.0);
+    let lookat: Point3 = Point3::new(0.0, 0.0, -1.0);
+    let vup: Vec3 = Vec3::new(0.0, 1.0, 0.0);
+    let dist_to_focus: f64 = (lookfrom - lookat).length();
+    let vfov: f64 = 35.0;
+    let aperture = 0.0;
+    let cam: Box<dyn Camera + Sync + Send> = Box::new(PerspectiveCamera::new(
         lookfrom,
         lookat,
         vup,
@@ -210,7 +583,7 @@ pub fn defocus_blur_scene() -> SceneSettings {
         aspect_ratio,
         aperture,
         dist_to_focus,
-    );
+    ));
 
     SceneSettings {
         world,
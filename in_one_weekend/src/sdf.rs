@@ -0,0 +1,284 @@
+use crate::aabb::Aabb;
+use crate::hittable::{HitRecord, Hittable};
+use crate::material::Material;
+use crate::ray::Ray;
+use crate::vec3::*;
+
+use std::sync::Arc;
+
+// signed distance to a surface: negative inside, positive outside
+pub trait Sdf {
+    fn distance(&self, p: Point3) -> f64;
+}
+
+pub struct SdfSphere {
+    pub center: Point3,
+    pub radius: f64,
+}
+
+impl SdfSphere {
+    pub fn new(center: Point3, radius: f64) -> SdfSphere {
+        SdfSphere { center, radius }
+    }
+}
+
+impl Sdf for SdfSphere {
+    fn distance(&self, p: Point3) -> f64 {
+        (p - self.center).length() - self.radius
+    }
+}
+
+// axis aligned box with the given half extents
+pub struct SdfBox {
+    pub center: Point3,
+    pub half_extents: Vec3,
+}
+
+impl SdfBox {
+    pub fn new(center: Point3, half_extents: Vec3) -> SdfBox {
+        SdfBox {
+            center,
+            half_extents,
+        }
+    }
+}
+
+impl Sdf for SdfBox {
+    fn distance(&self, p: Point3) -> f64 {
+        let d = p - self.center;
+        let q = Vec3::new(
+            d.x().abs() - self.half_extents.x(),
+            d.y().abs() - self.half_extents.y(),
+            d.z().abs() - self.half_extents.z(),
+        );
+        let outside = Vec3::new(q.x().max(0.0), q.y().max(0.0), q.z().max(0.0)).length();
+        let inside = q.x().max(q.y()).max(q.z()).min(0.0);
+        outside + inside
+    }
+}
+
+// torus around the y axis
+pub struct SdfTorus {
+    pub center: Point3,
+    pub major_radius: f64,
+    pub minor_radius: f64,
+}
+
+impl SdfTorus {
+    pub fn new(center: Point3, major_radius: f64, minor_radius: f64) -> SdfTorus {
+        SdfTorus {
+            center,
+            major_radius,
+            minor_radius,
+        }
+    }
+}
+
+impl Sdf for SdfTorus {
+    fn distance(&self, p: Point3) -> f64 {
+        let d = p - self.center;
+        let ring = (d.x() * d.x() + d.z() * d.z()).sqrt() - self.major_radius;
+        (ring * ring + d.y() * d.y()).sqrt() - self.minor_radius
+    }
+}
+
+// polynomial smooth minimum, k is the blend radius
+fn smooth_min(a: f64, b: f64, k: f64) -> f64 {
+    if k <= 0.0 {
+        return a.min(b);
+    }
+    let h = (0.5 + 0.5 * (b - a) / k).clamp(0.0, 1.0);
+    b + (a - b) * h - k * h * (1.0 - h)
+}
+
+// union of a and b with a rounded seam of radius k
+pub struct SmoothUnion {
+    pub a: Arc<dyn Sdf + Sync + Send>,
+    pub b: Arc<dyn Sdf + Sync + Send>,
+    pub k: f64,
+}
+
+impl SmoothUnion {
+    pub fn new(
+        a: Arc<dyn Sdf + Sync + Send>,
+        b: Arc<dyn Sdf + Sync + Send>,
+        k: f64,
+    ) -> SmoothUnion {
+        SmoothUnion { a, b, k }
+    }
+}
+
+impl Sdf for SmoothUnion {
+    fn distance(&self, p: Point3) -> f64 {
+        smooth_min(self.a.distance(p), self.b.distance(p), self.k)
+    }
+}
+
+// a with b carved out of it, with a rounded seam of radius k
+pub struct SmoothSubtraction {
+    pub a: Arc<dyn Sdf + Sync + Send>,
+    pub b: Arc<dyn Sdf + Sync + Send>,
+    pub k: f64,
+}
+
+impl SmoothSubtraction {
+    pub fn new(
+        a: Arc<dyn Sdf + Sync + Send>,
+        b: Arc<dyn Sdf + Sync + Send>,
+        k: f64,
+    ) -> SmoothSubtraction {
+        SmoothSubtraction { a, b, k }
+    }
+}
+
+impl Sdf for SmoothSubtraction {
+    fn distance(&self, p: Point3) -> f64 {
+        -smooth_min(-self.a.distance(p), self.b.distance(p), self.k)
+    }
+}
+
+// infinite repetition of a shape centered in a cell of size `period`
+// around the origin, a zero period component disables repetition on that axis
+pub struct Repeat {
+    pub sdf: Arc<dyn Sdf + Sync + Send>,
+    pub period: Vec3,
+}
+
+impl Repeat {
+    pub fn new(sdf: Arc<dyn Sdf + Sync + Send>, period: Vec3) -> Repeat {
+        Repeat { sdf, period }
+    }
+}
+
+impl Sdf for Repeat {
+    fn distance(&self, p: Point3) -> f64 {
+        let mut q = p;
+        for a in 0..3 {
+            let c = self.period[a];
+            if c > 0.0 {
+                q[a] = p[a] - c * (p[a] / c).round();
+            }
+        }
+        self.sdf.distance(q)
+    }
+}
+
+// twists a shape around the y axis by `rate` radians per unit of height.
+// the result is not an exact distance, so pair it with a smaller step scale
+pub struct Twist {
+    pub sdf: Arc<dyn Sdf + Sync + Send>,
+    pub rate: f64,
+}
+
+impl Twist {
+    pub fn new(sdf: Arc<dyn Sdf + Sync + Send>, rate: f64) -> Twist {
+        Twist { sdf, rate }
+    }
+}
+
+impl Sdf for Twist {
+    fn distance(&self, p: Point3) -> f64 {
+        let (s, c) = (self.rate * p.y()).sin_cos();
+        let q = Point3::new(c * p.x() - s * p.z(), p.y(), s * p.x() + c * p.z());
+        self.sdf.distance(q)
+    }
+}
+
+// renders an sdf by sphere tracing inside its bounding box
+pub struct SdfHittable {
+    pub sdf: Arc<dyn Sdf + Sync + Send>,
+    pub bounds: Aabb,
+    pub material: Arc<dyn Material + Sync + Send>,
+    // fraction of the distance bound taken per step, below 1 for
+    // operators that overestimate the distance
+    pub step_scale: f64,
+    pub max_steps: usize,
+    pub epsilon: f64,
+}
+
+// constructor and setter functions
+impl SdfHittable {
+    pub fn new(
+        sdf: Arc<dyn Sdf + Sync + Send>,
+        bounds: Aabb,
+        material: Arc<dyn Material + Sync + Send>,
+    ) -> SdfHittable {
+        SdfHittable {
+            sdf,
+            bounds,
+            material,
+            step_scale: 1.0,
+            max_steps: 256,
+            epsilon: 1e-5,
+        }
+    }
+
+    pub fn with_step_scale(mut self, step_scale: f64) -> SdfHittable {
+        self.step_scale = step_scale;
+        self
+    }
+}
+
+// member functions
+impl SdfHittable {
+    // central differences of the distance field
+    pub fn gradient(&self, p: Point3) -> Vec3 {
+        let h = self.epsilon * 10.0;
+        let mut g = Vec3::new(0.0, 0.0, 0.0);
+        for a in 0..3 {
+            let mut offset = Vec3::new(0.0, 0.0, 0.0);
+            offset[a] = h;
+            g[a] = self.sdf.distance(p + offset) - self.sdf.distance(p - offset);
+        }
+        g
+    }
+}
+
+impl Hittable for SdfHittable {
+    fn hit(&self, r: &Ray, t_min: f64, t_max: f64) -> Option<HitRecord> {
+        let (t0, t1) = self.bounds.hit_interval(r, t_min, t_max)?;
+        let ray_length = r.direction().length();
+
+        // march in units of the distance to whichever side we start on, and
+        // only accept a surface once the ray has moved clear of its origin
+        let side = if self.sdf.distance(r.at(t0)) < 0.0 {
+            -1.0
+        } else {
+            1.0
+        };
+        let mut t = t0;
+        let mut cleared = false;
+        for _ in 0..self.max_steps {
+            let d = side * self.sdf.distance(r.at(t));
+            if d < self.epsilon {
+                if cleared {
+                    let p = r.at(t);
+                    let mut hr = HitRecord {
+                        t,
+                        p,
+                        normal: Vec3::new(0.0, 0.0, 0.0),
+                        u: 0.0,
+                        v: 0.0,
+                        dpdu: Vec3::new(0.0, 0.0, 0.0),
+                        dpdv: Vec3::new(0.0, 0.0, 0.0),
+                        front_face: false,
+                        material: Arc::clone(&self.material),
+                    };
+                    hr.set_face_normal(r, unit_vector(self.gradient(p)));
+                    return Some(hr);
+                }
+            } else {
+                cleared = true;
+            }
+            t += (d * self.step_scale).max(self.epsilon) / ray_length;
+            if t > t1 {
+                return None;
+            }
+        }
+        None
+    }
+
+    fn bounding_box(&self) -> Option<Aabb> {
+        Some(self.bounds)
+    }
+}
@@ -0,0 +1,239 @@
+use crate::vec3::{Color, Vec3};
+
+use std::ops;
+use std::sync::OnceLock;
+
+// visible range covered by spectral rendering, in nanometers
+pub const LAMBDA_MIN: f64 = 380.0;
+pub const LAMBDA_MAX: f64 = 720.0;
+// wavelengths carried by each path
+pub const SPECTRUM_SAMPLES: usize = 4;
+
+// spectral quantity at the wavelengths of one path
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct SampledSpectrum {
+    pub values: [f64; SPECTRUM_SAMPLES],
+}
+
+impl SampledSpectrum {
+    pub fn new(values: [f64; SPECTRUM_SAMPLES]) -> SampledSpectrum {
+        SampledSpectrum { values }
+    }
+
+    pub fn constant(c: f64) -> SampledSpectrum {
+        SampledSpectrum::new([c; SPECTRUM_SAMPLES])
+    }
+}
+
+impl ops::Index<usize> for SampledSpectrum {
+    type Output = f64;
+
+    fn index(&self, i: usize) -> &f64 {
+        &self.values[i]
+    }
+}
+
+impl ops::Add for SampledSpectrum {
+    type Output = SampledSpectrum;
+
+    fn add(self, other: SampledSpectrum) -> SampledSpectrum {
+        let mut values = self.values;
+        for (v, o) in values.iter_mut().zip(other.values) {
+            *v += o;
+        }
+        SampledSpectrum::new(values)
+    }
+}
+
+impl ops::Mul for SampledSpectrum {
+    type Output = SampledSpectrum;
+
+    fn mul(self, other: SampledSpectrum) -> SampledSpectrum {
+        let mut values = self.values;
+        for (v, o) in values.iter_mut().zip(other.values) {
+            *v *= o;
+        }
+        SampledSpectrum::new(values)
+    }
+}
+
+// hero wavelength sampling: one uniformly sampled wavelength plus
+// others spaced evenly after it, wrapping around the visible range
+#[derive(Debug, Copy, Clone)]
+pub struct SampledWavelengths {
+    pub lambda: [f64; SPECTRUM_SAMPLES],
+    pub pdf: [f64; SPECTRUM_SAMPLES],
+}
+
+// constructor functions
+impl SampledWavelengths {
+    pub fn sample_uniform(u: f64) -> SampledWavelengths {
+        let range = LAMBDA_MAX - LAMBDA_MIN;
+        let mut lambda = [0.0; SPECTRUM_SAMPLES];
+        for (i, l) in lambda.iter_mut().enumerate() {
+            let offset = (u + i as f64 / SPECTRUM_SAMPLES as f64).fract();
+            *l = LAMBDA_MIN + offset * range;
+        }
+        SampledWavelengths {
+            lambda,
+            pdf: [1.0 / range; SPECTRUM_SAMPLES],
+        }
+    }
+}
+
+// member functions
+impl SampledWavelengths {
+    pub fn hero(&self) -> f64 {
+        self.lambda[0]
+    }
+
+    // after wavelength dependent scattering the other wavelengths no longer
+    // follow the same path, so only the hero wavelength carries on
+    pub fn terminate_secondary(&mut self) {
+        if self.secondary_terminated() {
+            return;
+        }
+        for pdf in self.pdf.iter_mut().skip(1) {
+            *pdf = 0.0;
+        }
+        self.pdf[0] /= SPECTRUM_SAMPLES as f64;
+    }
+
+    pub fn secondary_terminated(&self) -> bool {
+        self.pdf.iter().skip(1).all(|&p| p == 0.0)
+    }
+
+    // monte carlo estimate of the cie xyz response to a spectrum
+    pub fn to_xyz(&self, s: &SampledSpectrum) -> Vec3 {
+        let mut xyz = Vec3::new(0.0, 0.0, 0.0);
+        for i in 0..SPECTRUM_SAMPLES {
+            if self.pdf[i] > 0.0 {
+                xyz += (s[i] / self.pdf[i]) * cie_xyz(self.lambda[i]);
+            }
+        }
+        xyz / SPECTRUM_SAMPLES as f64
+    }
+
+    // linear srgb at the film, balanced so a flat spectrum is white
+    pub fn to_rgb(&self, s: &SampledSpectrum) -> Color {
+        let rgb = xyz_to_linear_srgb(self.to_xyz(s));
+        let white = white_point();
+        Color::new(
+            rgb.x() / white.x(),
+            rgb.y() / white.y(),
+            rgb.z() / white.z(),
+        )
+    }
+}
+
+// piecewise gaussian fit to the cie 1931 color matching functions,
+// from wyman, sloan and shirley 2013
+pub fn cie_xyz(lambda: f64) -> Vec3 {
+    let g = |mu: f64, sigma_lo: f64, sigma_hi: f64| {
+        let sigma = if lambda < mu { sigma_lo } else { sigma_hi };
+        let x = (lambda - mu) / sigma;
+        (-0.5 * x * x).exp()
+    };
+    Vec3::new(
+        1.056 * g(599.8, 37.9, 31.0) + 0.362 * g(442.0, 16.0, 26.7) - 0.065 * g(501.1, 20.4, 26.2),
+        0.821 * g(568.8, 46.9, 40.5) + 0.286 * g(530.9, 16.3, 31.1),
+        1.217 * g(437.0, 11.8, 36.0) + 0.681 * g(459.0, 26.0, 13.8),
+    )
+}
+
+pub fn xyz_to_linear_srgb(xyz: Vec3) -> Color {
+    Color::new(
+        3.2404542 * xyz.x() - 1.5371385 * xyz.y() - 0.4985314 * xyz.z(),
+        -0.9692660 * xyz.x() + 1.8760108 * xyz.y() + 0.0415560 * xyz.z(),
+        0.0556434 * xyz.x() - 0.2040259 * xyz.y() + 1.0572252 * xyz.z(),
+    )
+}
+
+// srgb response to a flat unit spectrum over the visible range
+fn white_point() -> Color {
+    static WHITE: OnceLock<Color> = OnceLock::new();
+    *WHITE.get_or_init(|| {
+        let steps = 3400;
+        let d_lambda = (LAMBDA_MAX - LAMBDA_MIN) / steps as f64;
+        let mut xyz = Vec3::new(0.0, 0.0, 0.0);
+        for i in 0..steps {
+            xyz += d_lambda * cie_xyz(LAMBDA_MIN + (i as f64 + 0.5) * d_lambda);
+        }
+        xyz_to_linear_srgb(xyz)
+    })
+}
+
+// red, green and blue basis spectra: smooth bands that sum to one
+// everywhere, so white upsamples to a flat spectrum and albedos in
+// [0, 1] stay physically valid reflectances
+pub fn rgb_basis(lambda: f64) -> Vec3 {
+    let step = |edge: f64| 1.0 / (1.0 + (-(lambda - edge) / 8.0).exp());
+    let blue = 1.0 - step(495.0);
+    let red = step(585.0);
+    Vec3::new(red, 1.0 - red - blue, blue)
+}
+
+// evaluates the spectrum of an rgb color at the sampled wavelengths
+pub fn rgb_to_spectrum(c: Color, lambda: &SampledWavelengths) -> SampledSpectrum {
+    let mut values = [0.0; SPECTRUM_SAMPLES];
+    for (v, &l) in values.iter_mut().zip(lambda.lambda.iter()) {
+        let basis = rgb_basis(l);
+        *v = c.x() * basis.x() + c.y() * basis.y() + c.z() * basis.z();
+    }
+    SampledSpectrum::new(values)
+}
+
+// wavelength dependent index of refraction, coefficients take the
+// wavelength in micrometers as is conventional
+#[derive(Debug, Copy, Clone)]
+pub enum Dispersion {
+    // n = a + b / lambda^2
+    Cauchy { a: f64, b: f64 },
+    // n^2 = 1 + sum b_i lambda^2 / (lambda^2 - c_i)
+    Sellmeier { b: [f64; 3], c: [f64; 3] },
+}
+
+// constructor functions
+impl Dispersion {
+    // schott n-bk7 crown glass
+    pub fn bk7() -> Dispersion {
+        Dispersion::Sellmeier {
+            b: [1.03961212, 0.231792344, 1.01046945],
+            c: [0.00600069867, 0.0200179144, 103.560653],
+        }
+    }
+
+    // schott sf11 dense flint, strongly dispersive
+    pub fn dense_flint() -> Dispersion {
+        Dispersion::Sellmeier {
+            b: [1.73759695, 0.313747346, 1.89878101],
+            c: [0.013188707, 0.0623068142, 155.23629],
+        }
+    }
+
+    pub fn fused_silica() -> Dispersion {
+        Dispersion::Sellmeier {
+            b: [0.6961663, 0.4079426, 0.8974794],
+            c: [0.00467914826, 0.0135120631, 97.9340025],
+        }
+    }
+}
+
+// member functions
+impl Dispersion {
+    pub fn ior(&self, lambda_nm: f64) -> f64 {
+        let l2 = (lambda_nm / 1000.0).powi(2);
+        match self {
+            Dispersion::Cauchy { a, b } => a + b / l2,
+            Dispersion::Sellmeier { b, c } => {
+                let n2 = 1.0 + (0..3).map(|i| b[i] * l2 / (l2 - c[i])).sum::<f64>();
+                n2.sqrt()
+            }
+        }
+    }
+
+    // index at the sodium d line, for rendering without wavelengths
+    pub fn ior_d(&self) -> f64 {
+        self.ior(589.3)
+    }
+}
@@ -1,6 +1,8 @@
+use crate::aabb::Aabb;
 use crate::hittable::{HitRecord, Hittable};
 use crate::material::Material;
 use crate::ray::Ray;
+use crate::rtweekend::PI;
 use crate::vec3::*;
 
 use std::sync::Arc;
@@ -20,6 +22,28 @@ impl Sphere {
             material,
         }
     }
+
+    // p: a point on the unit sphere centered at the origin
+    // u: returned value [0,1] of angle around the y axis from x=-1
+    // v: returned value [0,1] of angle from y=-1 to y=+1
+    pub fn get_sphere_uv(p: Point3) -> (f64, f64) {
+        let theta = (-p.y()).clamp(-1.0, 1.0).acos();
+        let phi = (-p.z()).atan2(p.x()) + PI;
+        (phi / (2.0 * PI), theta / PI)
+    }
+
+    // derivatives of the surface point with respect to the uvs above,
+    // for a point p on the unit sphere and the sphere's radius
+    pub fn get_sphere_tangents(p: Point3, radius: f64) -> (Vec3, Vec3) {
+        let dpdu = 2.0 * PI * radius * Vec3::new(p.z(), 0.0, -p.x());
+        let rho = (p.x() * p.x() + p.z() * p.z()).sqrt();
+        if rho < 1e-12 {
+            // v is singular at the poles
+            return (dpdu, Vec3::new(0.0, 0.0, 0.0));
+        }
+        let dpdv = PI * radius * Vec3::new(-p.x() * p.y() / rho, rho, -p.z() * p.y() / rho);
+        (dpdu, dpdv)
+    }
 }
 
 impl Hittable for Sphere {
@@ -47,11 +71,25 @@ impl Hittable for Sphere {
             t: root,
             p: r.at(root),
             normal: Vec3::new(0.0, 0.0, 0.0),
+            u: 0.0,
+            v: 0.0,
+            dpdu: Vec3::new(0.0, 0.0, 0.0),
+            dpdv: Vec3::new(0.0, 0.0, 0.0),
             front_face: false,
             material: Arc::clone(&self.material),
         };
         let outward_normal: Vec3 = (hr.p - self.center) / self.radius;
-        hr.set_face_normal(&r, outward_normal);
+        hr.set_face_normal(r, outward_normal);
+        (hr.u, hr.v) = Sphere::get_sphere_uv(outward_normal);
+        (hr.dpdu, hr.dpdv) = Sphere::get_sphere_tangents(outward_normal, self.radius);
         Some(hr)
     }
+
+    fn bounding_box(&self) -> Option<Aabb> {
+        let r = self.radius.abs();
+        Some(Aabb::new(
+            self.center - Vec3::new(r, r, r),
+            self.center + Vec3::new(r, r, r),
+        ))
+    }
 }
@@ -0,0 +1,230 @@
+use crate::camera::{Camera, PerspectiveCamera};
+use crate::panoramic::{equirectangular_angles, equirectangular_direction, view_basis};
+use crate::ray::Ray;
+use crate::vec3::*;
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Eye {
+    Left,
+    Right,
+}
+
+impl Eye {
+    // which way the eye sits along the rig's right axis
+    fn side(&self) -> f64 {
+        match self {
+            Eye::Left => -1.0,
+            Eye::Right => 1.0,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum StereoMode {
+    // eyes look the same way with off axis frustums that line up at the
+    // convergence distance, free of vertical parallax
+    Parallel,
+    // eyes rotate inwards to meet at the convergence distance
+    ToeIn,
+}
+
+// how both eyes share one output image
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum StereoLayout {
+    // left eye in the left half
+    SideBySide,
+    // left eye in the top half
+    OverUnder,
+}
+
+impl StereoLayout {
+    // aspect ratio of the whole image for eyes of the given aspect ratio
+    pub fn aspect_ratio(&self, eye_aspect_ratio: f64) -> f64 {
+        match self {
+            StereoLayout::SideBySide => 2.0 * eye_aspect_ratio,
+            StereoLayout::OverUnder => eye_aspect_ratio / 2.0,
+        }
+    }
+
+    // the eye seeing film position (s, t) of the whole image, and the
+    // position on that eye's film
+    pub fn split(&self, s: f64, t: f64) -> (Eye, f64, f64) {
+        match self {
+            StereoLayout::SideBySide if s < 0.5 => (Eye::Left, 2.0 * s, t),
+            StereoLayout::SideBySide => (Eye::Right, 2.0 * s - 1.0, t),
+            StereoLayout::OverUnder if t >= 0.5 => (Eye::Left, s, 2.0 * t - 1.0),
+            StereoLayout::OverUnder => (Eye::Right, s, 2.0 * t),
+        }
+    }
+}
+
+// pair of cameras rendered into a single side by side or over/under
+// image, so the usual render loop writes both eyes at once
+pub struct StereoRig {
+    pub left: Box<dyn Camera + Sync + Send>,
+    pub right: Box<dyn Camera + Sync + Send>,
+    pub layout: StereoLayout,
+}
+
+// constructor functions
+impl StereoRig {
+    pub fn new(
+        left: Box<dyn Camera + Sync + Send>,
+        right: Box<dyn Camera + Sync + Send>,
+        layout: StereoLayout,
+    ) -> StereoRig {
+        // the rig reports a single exposure for its whole film
+        debug_assert_eq!(left.exposure(), right.exposure());
+        StereoRig {
+            left,
+            right,
+            layout,
+        }
+    }
+
+    // two pinhole eyes interocular apart, centered on lookfrom, with zero
+    // parallax at convergence distance along the view direction. vfov and
+    // aspect_ratio are per eye
+    #[allow(clippy::too_many_arguments)]
+    pub fn perspective(
+        lookfrom: Point3,
+        lookat: Point3,
+        vup: Vec3,
+        vfov: f64,
+        aspect_ratio: f64,
+        interocular: f64,
+        convergence: f64,
+        mode: StereoMode,
+        layout: StereoLayout,
+    ) -> StereoRig {
+        let forward: Vec3 = unit_vector(lookat - lookfrom);
+        let right: Vec3 = unit_vector(cross(&forward, &vup));
+        let eye = |eye: Eye| -> Box<dyn Camera + Sync + Send> {
+            let offset: Vec3 = eye.side() * interocular / 2.0 * right;
+            match mode {
+                StereoMode::Parallel => Box::new(
+                    PerspectiveCamera::new(
+                        lookfrom,
+                        lookat,
+                        vup,
+                        vfov,
+                        aspect_ratio,
+                        0.0,
+                        convergence,
+                    )
+                    .with_eye_offset(offset),
+                ),
+                StereoMode::ToeIn => Box::new(PerspectiveCamera::new(
+                    lookfrom + offset,
+                    lookfrom + convergence * forward,
+                    vup,
+                    vfov,
+                    aspect_ratio,
+                    0.0,
+                    (convergence * forward - offset).length(),
+                )),
+            }
+        };
+        StereoRig::new(eye(Eye::Left), eye(Eye::Right), layout)
+    }
+
+    // omni-directional stereo pair of 360 degree panoramas, usually laid
+    // out over/under
+    pub fn ods(
+        lookfrom: Point3,
+        lookat: Point3,
+        vup: Vec3,
+        interocular: f64,
+        layout: StereoLayout,
+    ) -> StereoRig {
+        StereoRig::new(
+            Box::new(OdsCamera::new(
+                lookfrom,
+                lookat,
+                vup,
+                interocular,
+                Eye::Left,
+            )),
+            Box::new(OdsCamera::new(
+                lookfrom,
+                lookat,
+                vup,
+                interocular,
+                Eye::Right,
+            )),
+            layout,
+        )
+    }
+}
+
+// member functions
+impl StereoRig {
+    pub fn eye(&self, eye: Eye) -> &(dyn Camera + Sync + Send) {
+        match eye {
+            Eye::Left => self.left.as_ref(),
+            Eye::Right => self.right.as_ref(),
+        }
+    }
+}
+
+impl Camera for StereoRig {
+    fn get_ray(&self, s: f64, t: f64) -> Ray {
+        let (eye, s, t) = self.layout.split(s, t);
+        self.eye(eye).get_ray(s, t)
+    }
+
+    fn in_frame(&self, s: f64, t: f64) -> bool {
+        let (eye, s, t) = self.layout.split(s, t);
+        self.eye(eye).in_frame(s, t)
+    }
+
+    // both eyes share one exposure, checked in new
+    fn exposure(&self) -> f64 {
+        self.left.exposure()
+    }
+}
+
+// one eye of an omni-directional stereo panorama: an equirectangular
+// image whose rays start on a circle of interocular diameter, tangent to
+// it, so every viewing direction sees correct horizontal parallax. the
+// circle shrinks towards the poles to keep them from swirling
+pub struct OdsCamera {
+    center: Point3,
+    u: Vec3,
+    v: Vec3,
+    w: Vec3,
+    radius: f64,
+    pub eye: Eye,
+}
+
+impl OdsCamera {
+    pub fn new(
+        lookfrom: Point3,
+        lookat: Point3,
+        vup: Vec3,
+        interocular: f64,
+        eye: Eye,
+    ) -> OdsCamera {
+        let (u, v, w) = view_basis(lookfrom, lookat, vup);
+        OdsCamera {
+            center: lookfrom,
+            u,
+            v,
+            w,
+            radius: interocular / 2.0,
+            eye,
+        }
+    }
+}
+
+impl Camera for OdsCamera {
+    fn get_ray(&self, s: f64, t: f64) -> Ray {
+        let (longitude, latitude) = equirectangular_angles(s, t);
+        let direction: Vec3 =
+            equirectangular_direction(longitude, latitude, self.u, self.v, self.w);
+        // right of the horizontal viewing direction
+        let tangent: Vec3 = longitude.cos() * self.u + longitude.sin() * self.w;
+        let offset: Vec3 = self.eye.side() * self.radius * latitude.cos() * tangent;
+        Ray::new(self.center + offset, direction)
+    }
+}
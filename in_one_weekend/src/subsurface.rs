@@ -0,0 +1,174 @@
+use crate::fresnel::fresnel_dielectric;
+use crate::hittable::{HitRecord, Hittable};
+use crate::material::{transmittance, HenyeyGreenstein, Material, Output};
+use crate::onb::Onb;
+use crate::ray::Ray;
+use crate::rtweekend::{random_f64, INFINITY, PI};
+use crate::vec3::*;
+
+use std::sync::Arc;
+
+// most scattering events a path may take inside the object
+const MAX_STEPS: usize = 256;
+// steps before russian roulette starts culling dim paths
+const ROULETTE_DEPTH: usize = 8;
+
+// translucent object like skin, wax, marble or milk. light refracts into
+// the boundary, random walks through a homogeneous scattering medium and
+// leaves wherever it reaches the surface again. the boundary must be a
+// closed shape matching the object the material is applied to, in world
+// space; its own material is never used
+pub struct Subsurface {
+    pub boundary: Arc<dyn Hittable + Sync + Send>,
+    // single scattering albedo per channel
+    pub albedo: Color,
+    // mean distance between collisions per channel, in scene units
+    pub mean_free_path: Color,
+    pub ior: f64,
+    // henyey-greenstein anisotropy of the medium
+    pub g: f64,
+}
+
+// constructor functions
+impl Subsurface {
+    pub fn new(
+        boundary: Arc<dyn Hittable + Sync + Send>,
+        albedo: Color,
+        mean_free_path: Color,
+        ior: f64,
+    ) -> Subsurface {
+        Subsurface {
+            boundary,
+            albedo,
+            mean_free_path,
+            ior,
+            g: 0.0,
+        }
+    }
+
+    // forward scattering media such as skin have g around 0.8
+    pub fn with_anisotropy(
+        boundary: Arc<dyn Hittable + Sync + Send>,
+        albedo: Color,
+        mean_free_path: Color,
+        ior: f64,
+        g: f64,
+    ) -> Subsurface {
+        Subsurface {
+            g: g.clamp(-0.99, 0.99),
+            ..Subsurface::new(boundary, albedo, mean_free_path, ior)
+        }
+    }
+}
+
+// member functions
+impl Subsurface {
+    fn sigma_t(&self) -> Color {
+        let m = self.mean_free_path;
+        Color::new(
+            1.0 / m.x().max(1e-8),
+            1.0 / m.y().max(1e-8),
+            1.0 / m.z().max(1e-8),
+        )
+    }
+
+    // follows a path starting at p heading along direction inside the
+    // object until it leaves, returning the exit ray and path weight
+    fn walk(&self, r_in: &Ray, p: Point3, direction: Vec3) -> Option<Output> {
+        let sigma_t: Color = self.sigma_t();
+        let phase = HenyeyGreenstein::new(self.albedo, self.g);
+        let mut p: Point3 = p;
+        let mut direction: Vec3 = unit_vector(direction);
+        let mut throughput: Color = Color::new(1.0, 1.0, 1.0);
+
+        for step in 0..MAX_STEPS {
+            // distances are sampled from one channel picked in proportion
+            // to the path's throughput, weighted by the pdf averaged over
+            // all three the same way, which keeps chromatic paths bounded
+            let total: f64 = throughput.x() + throughput.y() + throughput.z();
+            if total <= 0.0 {
+                return None;
+            }
+            let pick: Color = throughput / total;
+            let xi: f64 = random_f64();
+            let channel: usize = if xi < pick.x() {
+                0
+            } else if xi < pick.x() + pick.y() {
+                1
+            } else {
+                2
+            };
+            let distance = -(1.0 - random_f64()).ln() / sigma_t[channel];
+
+            let mut inner: Ray = *r_in;
+            inner.orig = p;
+            inner.dir = direction;
+            let exit = self.boundary.hit(&inner, 0.001, INFINITY)?;
+
+            if exit.t <= distance {
+                let tr = transmittance(sigma_t, exit.t);
+                throughput = throughput * tr / dot(&pick, &tr);
+
+                // the hit's normal faces back into the object
+                let cos_theta: f64 = dot(&-direction, &exit.normal).min(1.0);
+                if random_f64() < fresnel_dielectric(cos_theta, 1.0 / self.ior) {
+                    p = exit.p;
+                    direction = reflect(direction, exit.normal);
+                    continue;
+                }
+                return Some(Output {
+                    attenuation: throughput,
+                    scattered: Ray::new(exit.p, refract(direction, exit.normal, self.ior)),
+                });
+            }
+
+            let tr = transmittance(sigma_t, distance);
+            let pdf = dot(&pick, &(sigma_t * tr));
+            throughput = throughput * self.albedo * sigma_t * tr / pdf;
+            p += distance * direction;
+
+            let cos_theta = phase.sample_cos_theta(random_f64());
+            let sin_theta = (1.0 - cos_theta * cos_theta).max(0.0).sqrt();
+            let phi = 2.0 * PI * random_f64();
+            direction = Onb::build_from_w(direction).local(
+                sin_theta * phi.cos(),
+                sin_theta * phi.sin(),
+                cos_theta,
+            );
+
+            if step >= ROULETTE_DEPTH {
+                let survive = throughput
+                    .x()
+                    .max(throughput.y())
+                    .max(throughput.z())
+                    .min(1.0);
+                if random_f64() >= survive {
+                    return None;
+                }
+                throughput /= survive;
+            }
+        }
+        None
+    }
+}
+
+impl Material for Subsurface {
+    fn scatter(&self, r_in: &Ray, rec: &HitRecord) -> Option<Output> {
+        let unit_direction: Vec3 = unit_vector(r_in.direction());
+
+        // a ray already inside, e.g. from a camera within the object
+        if !rec.front_face {
+            return self.walk(r_in, r_in.origin(), unit_direction);
+        }
+
+        let cos_theta: f64 = dot(&-unit_direction, &rec.normal).min(1.0);
+        if random_f64() < fresnel_dielectric(cos_theta, self.ior) {
+            return Some(Output {
+                attenuation: Color::new(1.0, 1.0, 1.0),
+                scattered: Ray::new(rec.p, reflect(unit_direction, rec.normal)),
+            });
+        }
+        let refracted = refract(unit_direction, rec.normal, 1.0 / self.ior);
+        self.walk(r_in, rec.p, refracted)
+    }
+}
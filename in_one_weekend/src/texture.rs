@@ -0,0 +1,105 @@
+use crate::image::Image;
+use crate::perlin::Perlin;
+use crate::vec3::{Color, Point3};
+
+use std::sync::Arc;
+
+pub trait Texture {
+    // color at surface coordinates (u, v) and hit point p
+    fn value(&self, u: f64, v: f64, p: &Point3) -> Color;
+}
+
+pub struct SolidColor {
+    pub color: Color,
+}
+
+impl SolidColor {
+    pub fn new(color: Color) -> SolidColor {
+        SolidColor { color }
+    }
+}
+
+impl Texture for SolidColor {
+    fn value(&self, _u: f64, _v: f64, _p: &Point3) -> Color {
+        self.color
+    }
+}
+
+// 3d checkerboard of cubes `1 / scale` wide
+pub struct Checker {
+    pub even: Arc<dyn Texture + Sync + Send>,
+    pub odd: Arc<dyn Texture + Sync + Send>,
+    pub scale: f64,
+}
+
+impl Checker {
+    pub fn new(
+        even: Arc<dyn Texture + Sync + Send>,
+        odd: Arc<dyn Texture + Sync + Send>,
+        scale: f64,
+    ) -> Checker {
+        Checker { even, odd, scale }
+    }
+
+    pub fn from_colors(even: Color, odd: Color, scale: f64) -> Checker {
+        Checker::new(
+            Arc::new(SolidColor::new(even)),
+            Arc::new(SolidColor::new(odd)),
+            scale,
+        )
+    }
+}
+
+impl Texture for Checker {
+    fn value(&self, u: f64, v: f64, p: &Point3) -> Color {
+        let cell = (self.scale * p.x()).floor() as i64
+            + (self.scale * p.y()).floor() as i64
+            + (self.scale * p.z()).floor() as i64;
+        if cell.rem_euclid(2) == 0 {
+            self.even.value(u, v, p)
+        } else {
+            self.odd.value(u, v, p)
+        }
+    }
+}
+
+// image looked up by surface coordinates
+pub struct ImageTexture {
+    pub image: Image,
+}
+
+impl ImageTexture {
+    pub fn new(image: Image) -> ImageTexture {
+        ImageTexture { image }
+    }
+}
+
+impl Texture for ImageTexture {
+    fn value(&self, u: f64, v: f64, _p: &Point3) -> Color {
+        self.image.sample(u, v)
+    }
+}
+
+// marble like veins of perlin turbulence
+pub struct NoiseTexture {
+    pub noise: Perlin,
+    pub scale: f64,
+    pub color: Color,
+}
+
+impl NoiseTexture {
+    pub fn new(scale: f64, color: Color) -> NoiseTexture {
+        NoiseTexture {
+            noise: Perlin::new(),
+            scale,
+            color,
+        }
+    }
+}
+
+impl Texture for NoiseTexture {
+    fn value(&self, _u: f64, _v: f64, p: &Point3) -> Color {
+        let phase = self.scale * p.z() + 10.0 * self.noise.turb(p, 7);
+        0.5 * (1.0 + phase.sin()) * self.color
+    }
+}
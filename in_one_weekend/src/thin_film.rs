@@ -0,0 +1,202 @@
+use crate::fresnel::ComplexIor;
+use crate::rtweekend::PI;
+use crate::vec3::Color;
+
+use std::ops;
+
+// wavelengths in nanometers the red, green and blue channels stand for
+pub const RGB_WAVELENGTHS: [f64; 3] = [650.0, 550.0, 450.0];
+
+// thin transparent coating, e.g. soap or oil, whose reflections interfere
+// and produce iridescence. thickness is in nanometers
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct ThinFilm {
+    pub thickness: f64,
+    pub ior: f64,
+}
+
+// constructor functions
+impl ThinFilm {
+    pub fn new(thickness: f64, ior: f64) -> ThinFilm {
+        ThinFilm { thickness, ior }
+    }
+}
+
+// member functions
+impl ThinFilm {
+    // airy reflectance of unpolarized light at one wavelength, arriving
+    // from a medium of index `outside` onto the film over a substrate with
+    // complex index eta + ik
+    pub fn reflectance_at(
+        &self,
+        cos_theta_i: f64,
+        outside: f64,
+        eta: f64,
+        k: f64,
+        lambda: f64,
+    ) -> f64 {
+        let cos_theta_i = cos_theta_i.clamp(0.0, 1.0);
+        let n1 = Complex::real(outside);
+        let n2 = Complex::real(self.ior);
+        let n3 = Complex::new(eta, k);
+
+        // snell's law with complex cosines, covering total internal
+        // reflection and absorbing substrates alike
+        let sin_i = outside * (1.0 - cos_theta_i * cos_theta_i).sqrt();
+        let cosine = |n: Complex| {
+            let s = Complex::real(sin_i) / n;
+            (Complex::real(1.0) - s * s).sqrt()
+        };
+        let cos1 = Complex::real(cos_theta_i);
+        let cos2 = cosine(n2);
+        let cos3 = cosine(n3);
+
+        // phase difference picked up by one round trip through the film
+        let delta = Complex::real(4.0 * PI * self.thickness / lambda) * n2 * cos2;
+        let phase = (Complex::new(0.0, 1.0) * delta).exp();
+
+        let airy = |r12: Complex, r23: Complex| {
+            let r = (r12 + r23 * phase) / (Complex::real(1.0) + r12 * r23 * phase);
+            r.norm_sqr()
+        };
+        let r_perp = airy(
+            interface_perp(n1, cos1, n2, cos2),
+            interface_perp(n2, cos2, n3, cos3),
+        );
+        let r_parl = airy(
+            interface_parl(n1, cos1, n2, cos2),
+            interface_parl(n2, cos2, n3, cos3),
+        );
+        ((r_perp + r_parl) / 2.0).clamp(0.0, 1.0)
+    }
+
+    // reflectance per rgb channel, or the same value in every channel
+    // at the ray's wavelength when rendering spectrally
+    pub fn reflectance(
+        &self,
+        cos_theta_i: f64,
+        outside: f64,
+        eta: Color,
+        k: Color,
+        wavelength: Option<f64>,
+    ) -> Color {
+        match wavelength {
+            Some(lambda) => {
+                let (eta, k) = interpolate_channels(eta, k, lambda);
+                let r = self.reflectance_at(cos_theta_i, outside, eta, k, lambda);
+                Color::new(r, r, r)
+            }
+            None => {
+                let [red, green, blue] = [0, 1, 2].map(|c| {
+                    self.reflectance_at(cos_theta_i, outside, eta[c], k[c], RGB_WAVELENGTHS[c])
+                });
+                Color::new(red, green, blue)
+            }
+        }
+    }
+
+    // film over a metal, seen from the surrounding air
+    pub fn reflectance_conductor(
+        &self,
+        cos_theta_i: f64,
+        ior: &ComplexIor,
+        wavelength: Option<f64>,
+    ) -> Color {
+        self.reflectance(cos_theta_i, 1.0, ior.eta, ior.k, wavelength)
+    }
+}
+
+// reads a per channel quantity at an arbitrary wavelength, linearly
+// between the channel wavelengths and clamped past them
+fn interpolate_channels(eta: Color, k: Color, lambda: f64) -> (f64, f64) {
+    let [red, green, blue] = RGB_WAVELENGTHS;
+    let (a, b, t) = if lambda >= green {
+        (1, 0, ((lambda - green) / (red - green)).min(1.0))
+    } else {
+        (1, 2, ((green - lambda) / (green - blue)).min(1.0))
+    };
+    ((1.0 - t) * eta[a] + t * eta[b], (1.0 - t) * k[a] + t * k[b])
+}
+
+// fresnel amplitude coefficients for light polarized perpendicular and
+// parallel to the plane of incidence
+fn interface_perp(na: Complex, cos_a: Complex, nb: Complex, cos_b: Complex) -> Complex {
+    (na * cos_a - nb * cos_b) / (na * cos_a + nb * cos_b)
+}
+
+fn interface_parl(na: Complex, cos_a: Complex, nb: Complex, cos_b: Complex) -> Complex {
+    (nb * cos_a - na * cos_b) / (nb * cos_a + na * cos_b)
+}
+
+// just enough complex arithmetic for the film equations
+#[derive(Debug, Copy, Clone)]
+struct Complex {
+    re: f64,
+    im: f64,
+}
+
+impl Complex {
+    fn new(re: f64, im: f64) -> Complex {
+        Complex { re, im }
+    }
+
+    fn real(re: f64) -> Complex {
+        Complex::new(re, 0.0)
+    }
+
+    fn norm_sqr(&self) -> f64 {
+        self.re * self.re + self.im * self.im
+    }
+
+    // principal square root
+    fn sqrt(&self) -> Complex {
+        let r = self.norm_sqr().sqrt();
+        let re = (0.5 * (r + self.re)).max(0.0).sqrt();
+        let im = (0.5 * (r - self.re)).max(0.0).sqrt();
+        Complex::new(re, if self.im < 0.0 { -im } else { im })
+    }
+
+    fn exp(&self) -> Complex {
+        let m = self.re.exp();
+        Complex::new(m * self.im.cos(), m * self.im.sin())
+    }
+}
+
+impl ops::Add for Complex {
+    type Output = Complex;
+
+    fn add(self, o: Complex) -> Complex {
+        Complex::new(self.re + o.re, self.im + o.im)
+    }
+}
+
+impl ops::Sub for Complex {
+    type Output = Complex;
+
+    fn sub(self, o: Complex) -> Complex {
+        Complex::new(self.re - o.re, self.im - o.im)
+    }
+}
+
+impl ops::Mul for Complex {
+    type Output = Complex;
+
+    fn mul(self, o: Complex) -> Complex {
+        Complex::new(
+            self.re * o.re - self.im * o.im,
+            self.re * o.im + self.im * o.re,
+        )
+    }
+}
+
+impl ops::Div for Complex {
+    type Output = Complex;
+
+    fn div(self, o: Complex) -> Complex {
+        let d = o.norm_sqr();
+        Complex::new(
+            (self.re * o.re + self.im * o.im) / d,
+            (self.im * o.re - self.re * o.im) / d,
+        )
+    }
+}
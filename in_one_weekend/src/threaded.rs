@@ -1,8 +1,8 @@
-use crate::clio::get_thread_parameters;
+use crate::clio::{get_thread_parameters, has_flag};
 use crate::color::write_color;
-use crate::ray::Ray;
-use crate::rtweekend::*;
-use crate::scenes::{ray_color, ImageSettings, SceneSettings};
+use crate::film::sample_pixel;
+use crate::npr::{render_outlined, Outline};
+use crate::scenes::{ImageSettings, SceneSettings};
 use crate::vec3::*;
 
 use std::io::{self, Write};
@@ -10,6 +10,9 @@ use std::sync::mpsc::{self, Receiver, Sender};
 use std::sync::Arc;
 use std::thread;
 
+type ColorSender = Sender<Vec<Color>>;
+type ColorReceiver = Receiver<Vec<Color>>;
+
 pub struct ThreadParameters {
     pub num_threads: usize,
     pub lines_per_thread: usize,
@@ -31,29 +34,11 @@ fn thread_work(thread_input: ThreadInput) {
         scene_settings,
         tx,
     } = thread_input;
-    let SceneSettings {
-        world,
-        cam,
-        image_settings,
-    } = &*scene_settings;
-    let ImageSettings {
-        aspect_ratio: _,
-        image_width,
-        image_height,
-        samples_per_pixel,
-        max_depth,
-    } = image_settings;
+    let image_width: i32 = scene_settings.image_settings.image_width;
     let mut v: Vec<Color> = vec![];
     for j in (line_idx..(line_idx + lines_per_thread as i32)).rev() {
-        for i in 0..(*image_width) {
-            let mut pixel_color: Color = Color::new(0.0, 0.0, 0.0);
-            for _ in 0..*samples_per_pixel {
-                let u: f64 = ((i as f64) + random_f64()) / ((*image_width - 1) as f64);
-                let v: f64 = ((j as f64) + random_f64()) / ((*image_height - 1) as f64);
-                let r: Ray = cam.get_ray(u, v);
-                pixel_color += ray_color(&r, &world, *max_depth);
-            }
-            v.push(pixel_color);
+        for i in 0..image_width {
+            v.push(sample_pixel(&scene_settings, i, j));
         }
     }
     tx.send(v).unwrap();
@@ -70,6 +55,7 @@ pub fn multi_threaded(scene_settings: SceneSettings, thread_params: ThreadParame
         image_height,
         samples_per_pixel,
         max_depth: _,
+        spectral: _,
     } = scene_settings.image_settings;
 
     let scene_settings: Arc<SceneSettings> = Arc::new(scene_settings);
@@ -90,11 +76,8 @@ pub fn multi_threaded(scene_settings: SceneSettings, thread_params: ThreadParame
             .ceil() as i32;
 
         // create vector of channels (1 per thread)
-        let (senders, receivers): (Vec<Sender<Vec<Color>>>, Vec<Receiver<Vec<Color>>>) = (0
-            ..num_threads)
-            .into_iter()
-            .map(|_| mpsc::channel())
-            .unzip();
+        let (senders, receivers): (Vec<ColorSender>, Vec<ColorReceiver>) =
+            (0..num_threads).map(|_| mpsc::channel()).unzip();
 
         for (thread_idx, sender) in senders.into_iter().enumerate() {
             let start_of_thread_work = i + thread_idx * lines_per_thread;
@@ -122,38 +105,30 @@ pub fn multi_threaded(scene_settings: SceneSettings, thread_params: ThreadParame
 }
 
 pub fn single_threaded(scene_settings: SceneSettings) {
-    let SceneSettings {
-        world,
-        cam,
-        image_settings,
-    } = scene_settings;
     let ImageSettings {
         image_height,
         image_width,
         samples_per_pixel,
-        max_depth,
         ..
-    } = image_settings;
+    } = scene_settings.image_settings;
 
     for j in (0..image_height).rev() {
         eprint!("\rScanlines remaining: {j} ");
         io::stderr().flush().unwrap();
         for i in 0..image_width {
-            let mut pixel_color: Color = Color::new(0.0, 0.0, 0.0);
-            for _ in 0..samples_per_pixel {
-                let u: f64 = ((i as f64) + random_f64()) / ((image_width - 1) as f64);
-                let v: f64 = ((j as f64) + random_f64()) / ((image_height - 1) as f64);
-                let r: Ray = cam.get_ray(u, v);
-                pixel_color += ray_color(&r, &world, max_depth);
-            }
-            write_color(pixel_color, samples_per_pixel);
+            write_color(sample_pixel(&scene_settings, i, j), samples_per_pixel);
         }
     }
 }
 
 // handle getting commandline multithreading parameters,
 // then calling associated single or multithreaded behavior
-pub fn render(scene_settings: SceneSettings) {
+pub fn render(mut scene_settings: SceneSettings) {
+    // --spectral switches any scene to spectral rendering
+    if has_flag("--spectral") {
+        scene_settings.image_settings.spectral = true;
+    }
+
     // print image header
     print!(
         "P3\n{} {}\n255\n",
@@ -163,7 +138,14 @@ pub fn render(scene_settings: SceneSettings) {
     // get parameters for multi threading if given
     let thread_params: ThreadParameters = get_thread_parameters();
 
-    if thread_params.num_threads == 1 {
+    // --outline draws toon style ink lines over the render
+    if has_flag("--outline") {
+        render_outlined(
+            scene_settings,
+            Outline::default(),
+            thread_params.num_threads,
+        );
+    } else if thread_params.num_threads == 1 {
         single_threaded(scene_settings);
     } else {
         multi_threaded(scene_settings, thread_params);
@@ -0,0 +1,107 @@
+use crate::aabb::Aabb;
+use crate::hittable::{HitRecord, Hittable};
+use crate::material::Material;
+use crate::polynomial::solve_quartic;
+use crate::ray::Ray;
+use crate::rtweekend::PI;
+use crate::vec3::*;
+
+use std::sync::Arc;
+
+// torus around the y axis through `center`, wrap it in an `Instance`
+// to orient it differently
+pub struct Torus {
+    pub center: Point3,
+    // distance from the center to the middle of the tube
+    pub major_radius: f64,
+    // radius of the tube
+    pub minor_radius: f64,
+    pub material: Arc<dyn Material + Sync + Send>,
+}
+
+// constructor and setter functions
+impl Torus {
+    pub fn new(
+        center: Point3,
+        major_radius: f64,
+        minor_radius: f64,
+        material: Arc<dyn Material + Sync + Send>,
+    ) -> Torus {
+        Torus {
+            center,
+            major_radius,
+            minor_radius,
+            material,
+        }
+    }
+}
+
+impl Hittable for Torus {
+    fn hit(&self, r: &Ray, t_min: f64, t_max: f64) -> Option<HitRecord> {
+        // cull with the bounding box before solving the quartic
+        if !self.bounding_box()?.hit(r, t_min, t_max) {
+            return None;
+        }
+
+        // solve along a unit direction for better conditioning
+        let len = r.direction().length();
+        let d: Vec3 = r.direction() / len;
+        let o: Vec3 = r.origin() - self.center;
+        let rr = self.major_radius * self.major_radius;
+
+        // (|p|^2 + R^2 - r^2)^2 = 4 R^2 (p.x^2 + p.z^2)
+        let e = dot(&o, &d);
+        let k = o.length_squared() + rr - self.minor_radius * self.minor_radius;
+        let dxz = d.x() * d.x() + d.z() * d.z();
+        let oxz = o.x() * d.x() + o.z() * d.z();
+        let pxz = o.x() * o.x() + o.z() * o.z();
+        let roots = solve_quartic(
+            1.0,
+            4.0 * e,
+            4.0 * e * e + 2.0 * k - 4.0 * rr * dxz,
+            4.0 * e * k - 8.0 * rr * oxz,
+            k * k - 4.0 * rr * pxz,
+        );
+
+        let t = roots
+            .into_iter()
+            .map(|s| s / len)
+            .filter(|&t| t_min <= t && t <= t_max)
+            .min_by(|a, b| a.total_cmp(b))?;
+
+        let p = o + (t * len) * d;
+        let ring_dir = unit_vector(Vec3::new(p.x(), 0.0, p.z()));
+        let outward_normal = unit_vector(p - self.major_radius * ring_dir);
+
+        // u: angle around the y axis, v: angle around the tube
+        let phi = p.z().atan2(p.x());
+        let theta = p
+            .y()
+            .atan2((p.x() * p.x() + p.z() * p.z()).sqrt() - self.major_radius);
+        let mut hr = HitRecord {
+            t,
+            p: r.at(t),
+            normal: Vec3::new(0.0, 0.0, 0.0),
+            u: (phi + PI) / (2.0 * PI),
+            v: (theta + PI) / (2.0 * PI),
+            dpdu: Vec3::new(0.0, 0.0, 0.0),
+            dpdv: Vec3::new(0.0, 0.0, 0.0),
+            front_face: false,
+            material: Arc::clone(&self.material),
+        };
+        hr.set_face_normal(r, outward_normal);
+        // around the y axis, then around the tube
+        hr.dpdu = 2.0 * PI * Vec3::new(-p.z(), 0.0, p.x());
+        hr.dpdv = 2.0
+            * PI
+            * self.minor_radius
+            * (-theta.sin() * ring_dir + theta.cos() * Vec3::new(0.0, 1.0, 0.0));
+        Some(hr)
+    }
+
+    fn bounding_box(&self) -> Option<Aabb> {
+        let outer = self.major_radius + self.minor_radius;
+        let e = Vec3::new(outer, self.minor_radius, outer);
+        Some(Aabb::new(self.center - e, self.center + e))
+    }
+}
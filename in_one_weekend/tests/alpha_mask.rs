@@ -0,0 +1,125 @@
+#[cfg(test)]
+mod tests {
+    use in_one_weekend::alpha_mask::{AlphaMask, AlphaMode};
+    use in_one_weekend::hittable::Hittable;
+    use in_one_weekend::hittable_list::HittableList;
+    use in_one_weekend::image::Image;
+    use in_one_weekend::material::{Lambertian, Material};
+    use in_one_weekend::quad::Quad;
+    use in_one_weekend::ray::Ray;
+    use in_one_weekend::scenes::ray_color;
+    use in_one_weekend::sphere::Sphere;
+    use in_one_weekend::texture::{ImageTexture, SolidColor, Texture};
+    use in_one_weekend::vec3::*;
+
+    use std::sync::Arc;
+
+    // opaque only behind the xy plane
+    struct BackHalf;
+
+    impl Texture for BackHalf {
+        fn value(&self, _u: f64, _v: f64, p: &Point3) -> Color {
+            if p.z() < 0.0 {
+                Color::new(1.0, 1.0, 1.0)
+            } else {
+                Color::new(0.0, 0.0, 0.0)
+            }
+        }
+    }
+
+    fn gray() -> Arc<dyn Material + Sync + Send> {
+        Arc::new(Lambertian::new(Color::new(0.5, 0.5, 0.5)))
+    }
+
+    // unit quad in the plane z = depth
+    fn quad(depth: f64) -> Quad {
+        Quad::new(
+            Point3::new(0.0, 0.0, depth),
+            Vec3::new(1.0, 0.0, 0.0),
+            Vec3::new(0.0, 1.0, 0.0),
+            gray(),
+        )
+    }
+
+    #[test]
+    fn quad_test() {
+        let q = quad(0.0);
+        let r: Ray = Ray::new(Point3::new(0.25, 0.75, 1.0), Vec3::new(0.0, 0.0, -1.0));
+        let rec = q.hit(&r, 0.001, f64::INFINITY).unwrap();
+        assert!((rec.t - 1.0).abs() < 1e-12);
+        assert!((rec.u - 0.25).abs() < 1e-12 && (rec.v - 0.75).abs() < 1e-12);
+        assert!(rec.front_face);
+        let r: Ray = Ray::new(Point3::new(1.5, 0.5, 1.0), Vec3::new(0.0, 0.0, -1.0));
+        assert!(q.hit(&r, 0.001, f64::INFINITY).is_none());
+        assert!(q.bounding_box().is_some());
+    }
+
+    #[test]
+    fn threshold_cutout_test() {
+        // left half opaque, right half empty
+        let image = Image::new(
+            2,
+            1,
+            vec![Color::new(1.0, 1.0, 1.0), Color::new(0.0, 0.0, 0.0)],
+        );
+        let leaf = AlphaMask::new(
+            Arc::new(quad(0.0)),
+            Arc::new(ImageTexture::new(image)),
+            AlphaMode::Threshold(0.5),
+        );
+        let mut world = HittableList::new();
+        world.add(Box::new(leaf));
+        world.add(Box::new(quad(-1.0)));
+
+        let through_left: Ray = Ray::new(Point3::new(0.25, 0.5, 1.0), Vec3::new(0.0, 0.0, -1.0));
+        let rec = world.hit(&through_left, 0.001, f64::INFINITY).unwrap();
+        assert!((rec.t - 1.0).abs() < 1e-9);
+        let through_right: Ray = Ray::new(Point3::new(0.75, 0.5, 1.0), Vec3::new(0.0, 0.0, -1.0));
+        let rec = world.hit(&through_right, 0.001, f64::INFINITY).unwrap();
+        assert!((rec.t - 2.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn skips_to_later_surfaces_of_the_object_test() {
+        let shell = AlphaMask::new(
+            Arc::new(Sphere::new(Point3::new(0.0, 0.0, 0.0), 1.0, gray())),
+            Arc::new(BackHalf),
+            AlphaMode::Threshold(0.5),
+        );
+        let r: Ray = Ray::new(Point3::new(0.0, 0.0, 5.0), Vec3::new(0.0, 0.0, -1.0));
+        let rec = shell.hit(&r, 0.001, f64::INFINITY).unwrap();
+        assert!((rec.t - 6.0).abs() < 1e-9);
+        assert!(!rec.front_face);
+    }
+
+    #[test]
+    fn stochastic_opacity_test() {
+        let mask = AlphaMask::new(
+            Arc::new(quad(0.0)),
+            Arc::new(SolidColor::new(Color::new(0.3, 0.3, 0.3))),
+            AlphaMode::Stochastic,
+        );
+        let r: Ray = Ray::new(Point3::new(0.5, 0.5, 1.0), Vec3::new(0.0, 0.0, -1.0));
+        let n = 20000;
+        let hits = (0..n)
+            .filter(|_| mask.hit(&r, 0.001, f64::INFINITY).is_some())
+            .count();
+        assert!((hits as f64 / n as f64 - 0.3).abs() < 0.02);
+    }
+
+    #[test]
+    fn no_bounce_depth_used_test() {
+        // with one bounce left, a hole still shows the sky behind it
+        let hole = AlphaMask::new(
+            Arc::new(quad(0.0)),
+            Arc::new(SolidColor::new(Color::new(0.0, 0.0, 0.0))),
+            AlphaMode::Threshold(0.5),
+        );
+        let mut world = HittableList::new();
+        world.add(Box::new(hole));
+        let r: Ray = Ray::new(Point3::new(0.5, 0.5, 1.0), Vec3::new(0.0, 0.0, -1.0));
+        let seen = ray_color(&r, &world, 1);
+        assert_eq!(seen, ray_color(&r, &HittableList::new(), 1));
+        assert!(seen.length() > 0.0);
+    }
+}
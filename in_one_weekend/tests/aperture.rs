@@ -0,0 +1,119 @@
+#[cfg(test)]
+mod tests {
+    use in_one_weekend::aperture::*;
+    use in_one_weekend::camera::{Camera, PerspectiveCamera};
+    use in_one_weekend::image::Image;
+    use in_one_weekend::vec3::*;
+
+    // whether p lies inside the regular polygon the aperture describes
+    fn in_polygon(p: Vec3, blades: u32, rotation: f64) -> bool {
+        let step = 2.0 * std::f64::consts::PI / blades as f64;
+        (0..blades).all(|k| {
+            let theta = rotation.to_radians() + k as f64 * step;
+            let a = Vec3::new(theta.cos(), theta.sin(), 0.0);
+            let b = Vec3::new((theta + step).cos(), (theta + step).sin(), 0.0);
+            // counterclockwise edges keep the inside on their left
+            cross(&(b - a), &(p - a)).z() >= -1e-12
+        })
+    }
+
+    #[test]
+    fn circular_in_unit_disk_test() {
+        let aperture = Aperture::Circular;
+        for _ in 0..1000 {
+            let p = aperture.sample();
+            assert!(p.length() < 1.0);
+            assert_eq!(p.z(), 0.0);
+        }
+    }
+
+    #[test]
+    fn polygon_samples_inside_blades_test() {
+        for (blades, rotation) in [(3, 0.0), (5, 18.0), (6, 30.0), (9, 7.0)] {
+            let aperture = Aperture::polygon(blades, rotation).unwrap();
+            for _ in 0..2000 {
+                let p = aperture.sample();
+                assert!(in_polygon(p, blades, rotation));
+            }
+        }
+    }
+
+    #[test]
+    fn degenerate_apertures_test() {
+        // fewer than three blades enclose nothing
+        assert!(Aperture::polygon(2, 0.0).is_none());
+        assert!(Aperture::polygon(0, 0.0).is_none());
+
+        // neither does a mask without open pixels
+        let black = Color::new(0.0, 0.0, 0.0);
+        assert!(Aperture::mask(&Image::new(2, 1, vec![black, black])).is_none());
+        assert!(ApertureMask::new(&Image::new(0, 0, vec![])).is_none());
+    }
+
+    #[test]
+    fn polygon_is_uniform_test() {
+        // four blades turned 45 degrees make an axis aligned square
+        let aperture = Aperture::polygon(4, 45.0).unwrap();
+        let half = 0.5f64.sqrt();
+        let n = 20000;
+        let mut quadrant = 0;
+        let mut inner = 0;
+        for _ in 0..n {
+            let p = aperture.sample();
+            assert!(p.x().abs() <= half + 1e-12 && p.y().abs() <= half + 1e-12);
+            if p.x() > 0.0 && p.y() > 0.0 {
+                quadrant += 1;
+            }
+            if p.x().abs() < half / 2.0 && p.y().abs() < half / 2.0 {
+                inner += 1;
+            }
+        }
+        assert!((quadrant as f64 / n as f64 - 0.25).abs() < 0.02);
+        // the middle square covers a quarter of the area
+        assert!((inner as f64 / n as f64 - 0.25).abs() < 0.02);
+    }
+
+    #[test]
+    fn mask_follows_image_test() {
+        let black = Color::new(0.0, 0.0, 0.0);
+        let white = Color::new(1.0, 1.0, 1.0);
+
+        // only the top right pixel is open
+        let image = Image::new(2, 2, vec![black, white, black, black]);
+        let aperture = Aperture::mask(&image).unwrap();
+        for _ in 0..1000 {
+            let p = aperture.sample();
+            assert!(p.x() >= 0.0 && p.x() <= 1.0);
+            assert!(p.y() >= 0.0 && p.y() <= 1.0);
+        }
+
+        // pixels are picked in proportion to their brightness
+        let gray = Color::new(1.0 / 3.0, 1.0 / 3.0, 1.0 / 3.0);
+        let image = Image::new(2, 1, vec![gray, white]);
+        let aperture = Aperture::mask(&image).unwrap();
+        let n = 20000;
+        let left = (0..n).filter(|_| aperture.sample().x() < 0.0).count();
+        assert!((left as f64 / n as f64 - 0.25).abs() < 0.02);
+    }
+
+    #[test]
+    fn camera_lens_uses_aperture_test() {
+        let lookfrom = Point3::new(0.0, 0.0, 0.0);
+        let cam = PerspectiveCamera::new(
+            lookfrom,
+            Point3::new(0.0, 0.0, -1.0),
+            Vec3::new(0.0, 1.0, 0.0),
+            40.0,
+            1.0,
+            2.0,
+            5.0,
+        )
+        .with_aperture(Aperture::polygon(6, 0.0).unwrap());
+        for _ in 0..1000 {
+            // the camera looks down -z, so the lens lies in the xy plane
+            let r = cam.get_ray(0.3, 0.6);
+            assert!(r.origin().z().abs() < 1e-12);
+            assert!(in_polygon(r.origin(), 6, 0.0));
+        }
+    }
+}
@@ -6,7 +6,7 @@ mod tests {
 
     #[test]
     fn get_ray_test_one() {
-        let cam: Camera = Camera::new(
+        let cam: PerspectiveCamera = PerspectiveCamera::new(
             Point3::new(0.0, 0.0, 0.0),
             Point3::new(0.0, 0.0, -1.0),
             Vec3::new(0.0, 1.0, 0.0),
@@ -22,7 +22,7 @@ mod tests {
 
     #[test]
     fn get_ray_test_two() {
-        let cam: Camera = Camera::new(
+        let cam: PerspectiveCamera = PerspectiveCamera::new(
             Point3::new(0.0, 0.0, 0.0),
             Point3::new(0.0, 0.0, -1.0),
             Vec3::new(0.0, 1.0, 0.0),
@@ -43,4 +43,108 @@ mod tests {
             cam.lower_left_corner() + u * cam.horizontal() + v * cam.vertical() - cam.origin()
         );
     }
+
+    #[test]
+    fn orthographic_rays_are_parallel_test() {
+        let cam: OrthographicCamera = OrthographicCamera::new(
+            Point3::new(0.0, 0.0, 5.0),
+            Point3::new(0.0, 0.0, 0.0),
+            Vec3::new(0.0, 1.0, 0.0),
+            4.0,
+            2.0,
+        );
+        let a: Ray = cam.get_ray(0.0, 0.0);
+        let b: Ray = cam.get_ray(1.0, 1.0);
+        assert_eq!(a.direction(), Vec3::new(0.0, 0.0, -1.0));
+        assert_eq!(b.direction(), a.direction());
+
+        // the view spans view_width across and view_width / aspect up
+        assert_eq!(a.origin(), Point3::new(-2.0, -1.0, 5.0));
+        assert_eq!(b.origin(), Point3::new(2.0, 1.0, 5.0));
+        assert_eq!(cam.get_ray(0.5, 0.5).origin(), Point3::new(0.0, 0.0, 5.0));
+    }
+
+    #[test]
+    fn cameras_share_trait_test() {
+        let cams: Vec<Box<dyn Camera + Sync + Send>> = vec![
+            Box::new(PerspectiveCamera::new(
+                Point3::new(0.0, 0.0, 5.0),
+                Point3::new(0.0, 0.0, 0.0),
+                Vec3::new(0.0, 1.0, 0.0),
+                40.0,
+                1.0,
+                0.0,
+                5.0,
+            )),
+            Box::new(OrthographicCamera::new(
+                Point3::new(0.0, 0.0, 5.0),
+                Point3::new(0.0, 0.0, 0.0),
+                Vec3::new(0.0, 1.0, 0.0),
+                2.0,
+                1.0,
+            )),
+        ];
+        // the center of the film looks straight at lookat either way
+        for cam in cams.iter() {
+            let r: Ray = cam.get_ray(0.5, 0.5);
+            let d: Vec3 = unit_vector(r.direction());
+            assert!((d - Vec3::new(0.0, 0.0, -1.0)).length() < 1e-12);
+            assert!(r.origin().x().abs() < 1e-12 && r.origin().y().abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn physical_settings_test() {
+        // a 50mm lens on full frame sees about 27 degrees vertically
+        let settings = PhysicalSettings::full_frame(50.0, 2.0, 1.0 / 100.0, 100.0);
+        assert!((settings.vfov() - 26.9915).abs() < 1e-3);
+        assert_eq!(settings.aspect_ratio(), 1.5);
+        // 50 / 2 = 25mm entrance pupil
+        assert!((settings.aperture() - 0.025).abs() < 1e-12);
+    }
+
+    #[test]
+    fn sunny_sixteen_exposure_test() {
+        let sunny = PhysicalSettings::full_frame(50.0, 16.0, 1.0 / 100.0, 100.0);
+        assert!((sunny.exposure() - 1.0).abs() < 1e-12);
+        // any equivalent exposure gives the same film
+        let equivalent = PhysicalSettings::full_frame(50.0, 8.0, 1.0 / 400.0, 100.0);
+        assert!((equivalent.exposure() - 1.0).abs() < 1e-12);
+        // each stop doubles the light
+        let slower = PhysicalSettings::full_frame(50.0, 16.0, 1.0 / 50.0, 100.0);
+        assert!((slower.exposure() - 2.0).abs() < 1e-12);
+        let faster_film = PhysicalSettings::full_frame(50.0, 16.0, 1.0 / 100.0, 400.0);
+        assert!((faster_film.exposure() - 4.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn physical_camera_test() {
+        let settings = PhysicalSettings::full_frame(35.0, 4.0, 1.0 / 60.0, 200.0);
+        let cam: PerspectiveCamera = PerspectiveCamera::physical(
+            Point3::new(0.0, 0.0, 0.0),
+            Point3::new(0.0, 0.0, -1.0),
+            Vec3::new(0.0, 1.0, 0.0),
+            &settings,
+            3.0,
+        );
+        assert_eq!(cam.exposure(), settings.exposure());
+        let reference: PerspectiveCamera = PerspectiveCamera::new(
+            Point3::new(0.0, 0.0, 0.0),
+            Point3::new(0.0, 0.0, -1.0),
+            Vec3::new(0.0, 1.0, 0.0),
+            settings.vfov(),
+            1.5,
+            0.0,
+            3.0,
+        );
+        assert!((cam.horizontal() - reference.horizontal()).length() < 1e-12);
+        assert!((cam.vertical() - reference.vertical()).length() < 1e-12);
+        assert_eq!(reference.exposure(), 1.0);
+
+        // rays leave from within the entrance pupil
+        for _ in 0..100 {
+            let r: Ray = cam.get_ray(0.5, 0.5);
+            assert!(r.origin().length() <= settings.aperture() / 2.0 + 1e-12);
+        }
+    }
 }
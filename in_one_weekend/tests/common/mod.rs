@@ -0,0 +1,22 @@
+use in_one_weekend::hittable::HitRecord;
+use in_one_weekend::material::{Lambertian, Material};
+use in_one_weekend::vec3::*;
+
+use std::sync::Arc;
+
+// hit at the origin for testing a material on its own. the record's own
+// material is never used
+pub fn record(normal: Vec3, front_face: bool) -> HitRecord {
+    let m: Arc<dyn Material + Sync + Send> = Arc::new(Lambertian::new(Color::new(0.0, 0.0, 0.0)));
+    HitRecord {
+        p: Point3::new(0.0, 0.0, 0.0),
+        normal,
+        t: 1.0,
+        u: 0.0,
+        v: 0.0,
+        dpdu: Vec3::new(0.0, 0.0, 0.0),
+        dpdv: Vec3::new(0.0, 0.0, 0.0),
+        front_face,
+        material: m,
+    }
+}
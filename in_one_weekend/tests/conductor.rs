@@ -0,0 +1,61 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::record;
+    use in_one_weekend::fresnel::*;
+    use in_one_weekend::material::{Conductor, Material, RoughConductor};
+    use in_one_weekend::ray::Ray;
+    use in_one_weekend::vec3::*;
+
+    #[test]
+    fn complex_fresnel_test() {
+        // without absorption it matches the dielectric formula
+        for cos in [0.1, 0.5, 0.9, 1.0] {
+            assert!((fresnel_complex(cos, 1.5, 0.0) - fresnel_dielectric(cos, 1.5)).abs() < 1e-9);
+        }
+        // normal incidence: ((eta - 1)^2 + k^2) / ((eta + 1)^2 + k^2)
+        let expected = (0.5f64.powi(2) + 9.0) / (2.5f64.powi(2) + 9.0);
+        assert!((fresnel_complex(1.0, 1.5, 3.0) - expected).abs() < 1e-9);
+        assert!((fresnel_complex(0.0, 0.2, 3.9) - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn presets_test() {
+        let gold = ComplexIor::gold().reflectance(1.0);
+        // gold reflects red far more than blue
+        assert!(gold.x() > 0.9 && gold.z() < 0.5);
+        for ior in [
+            ComplexIor::gold(),
+            ComplexIor::silver(),
+            ComplexIor::copper(),
+            ComplexIor::aluminum(),
+            ComplexIor::iron(),
+        ] {
+            let head_on = ior.reflectance(1.0);
+            let grazing = ior.reflectance(0.01);
+            for a in 0..3 {
+                assert!(head_on[a] > 0.0 && head_on[a] < 1.0);
+                assert!(grazing[a] > head_on[a]);
+            }
+        }
+    }
+
+    #[test]
+    fn conductor_scatter_test() {
+        let gold = Conductor::new(ComplexIor::gold());
+        let rec = record(Vec3::new(0.0, 1.0, 0.0), true);
+        let r_in: Ray = Ray::new(Point3::new(0.0, 1.0, 0.0), Vec3::new(0.0, -2.0, 0.0));
+
+        let out = gold.scatter(&r_in, &rec).unwrap();
+        assert_eq!(
+            unit_vector(out.scattered.direction()),
+            Vec3::new(0.0, 1.0, 0.0)
+        );
+        assert_eq!(out.attenuation, ComplexIor::gold().reflectance(1.0));
+
+        let rough = RoughConductor::from_ior(ComplexIor::gold(), 0.0);
+        let out = rough.scatter(&r_in, &rec).unwrap();
+        assert!((out.attenuation - ComplexIor::gold().reflectance(1.0)).length() < 1e-3);
+    }
+}
@@ -0,0 +1,60 @@
+#[cfg(test)]
+mod tests {
+    use in_one_weekend::constant_medium::ConstantMedium;
+    use in_one_weekend::hittable::{HitRecord, Hittable};
+    use in_one_weekend::material::{HenyeyGreenstein, Lambertian, Material};
+    use in_one_weekend::ray::Ray;
+    use in_one_weekend::sphere::Sphere;
+    use in_one_weekend::vec3::*;
+
+    use std::sync::Arc;
+
+    fn boundary() -> Arc<dyn Hittable + Sync + Send> {
+        let m: Arc<dyn Material + Sync + Send> =
+            Arc::new(Lambertian::new(Color::new(0.0, 0.0, 0.0)));
+        Arc::new(Sphere::new(Point3::new(0.0, 0.0, 0.0), 1.0, m))
+    }
+
+    #[test]
+    fn dense_medium_scatters_at_entry_test() {
+        let medium = ConstantMedium::with_color(boundary(), 1e9, Color::new(1.0, 1.0, 1.0));
+        let r: Ray = Ray::new(Point3::new(-5.0, 0.0, 0.0), Vec3::new(1.0, 0.0, 0.0));
+
+        let hit: HitRecord = medium.hit(&r, 0.001, f64::INFINITY).unwrap();
+        assert!((hit.t - 4.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn ray_starting_inside_test() {
+        let medium = ConstantMedium::with_color(boundary(), 1e9, Color::new(1.0, 1.0, 1.0));
+        let r: Ray = Ray::new(Point3::new(0.0, 0.0, 0.0), Vec3::new(1.0, 0.0, 0.0));
+
+        let hit: HitRecord = medium.hit(&r, 0.001, f64::INFINITY).unwrap();
+        assert!(hit.t < 0.01);
+    }
+
+    #[test]
+    fn thin_medium_passes_through_test() {
+        let medium = ConstantMedium::with_color(boundary(), 1e-12, Color::new(1.0, 1.0, 1.0));
+        let r: Ray = Ray::new(Point3::new(-5.0, 0.0, 0.0), Vec3::new(1.0, 0.0, 0.0));
+        assert!(medium.hit(&r, 0.001, f64::INFINITY).is_none());
+
+        let missing: Ray = Ray::new(Point3::new(-5.0, 3.0, 0.0), Vec3::new(1.0, 0.0, 0.0));
+        assert!(medium.hit(&missing, 0.001, f64::INFINITY).is_none());
+    }
+
+    #[test]
+    fn henyey_greenstein_sampling_test() {
+        let forward = HenyeyGreenstein::new(Color::new(1.0, 1.0, 1.0), 0.9);
+        assert_eq!(forward.sample_cos_theta(0.0), 1.0);
+        assert_eq!(forward.sample_cos_theta(1.0), -1.0);
+        // most of the lobe points forward
+        assert!(forward.sample_cos_theta(0.5) > 0.8);
+        assert!(forward.phase(1.0) > forward.phase(-1.0));
+
+        let isotropic = HenyeyGreenstein::new(Color::new(1.0, 1.0, 1.0), 0.0);
+        assert!((isotropic.sample_cos_theta(0.5)).abs() < 1e-12);
+        let expected = 1.0 / (4.0 * std::f64::consts::PI);
+        assert!((isotropic.phase(0.3) - expected).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,76 @@
+#[cfg(test)]
+mod tests {
+    use in_one_weekend::aabb::Aabb;
+    use in_one_weekend::csg::Csg;
+    use in_one_weekend::hittable::{HitRecord, Hittable};
+    use in_one_weekend::material::{Lambertian, Material};
+    use in_one_weekend::ray::Ray;
+    use in_one_weekend::sphere::Sphere;
+    use in_one_weekend::vec3::*;
+
+    use std::sync::Arc;
+
+    fn sphere(center: Point3, radius: f64) -> Arc<dyn Hittable + Sync + Send> {
+        let m: Arc<dyn Material + Sync + Send> =
+            Arc::new(Lambertian::new(Color::new(0.0, 0.0, 0.0)));
+        Arc::new(Sphere::new(center, radius, m))
+    }
+
+    // (t, front_face) of every hit along the ray
+    fn all_hits(object: &dyn Hittable, r: &Ray) -> Vec<(f64, bool)> {
+        let mut out = vec![];
+        let mut t_min = 0.001;
+        while let Some(rec) = object.hit(r, t_min, f64::INFINITY) {
+            out.push((rec.t, rec.front_face));
+            t_min = rec.t + 0.001;
+        }
+        out
+    }
+
+    #[test]
+    fn hollow_shell_test() {
+        let origin: Point3 = Point3::new(0.0, 0.0, 0.0);
+        let shell = Csg::difference(sphere(origin, 2.0), sphere(origin, 1.0));
+        let r: Ray = Ray::new(Point3::new(-5.0, 0.0, 0.0), Vec3::new(1.0, 0.0, 0.0));
+
+        assert_eq!(
+            all_hits(&shell, &r),
+            vec![(3.0, true), (4.0, false), (6.0, true), (7.0, false)]
+        );
+        // the carved surface faces into the hole
+        let hit: HitRecord = shell.hit(&r, 3.5, f64::INFINITY).unwrap();
+        assert_eq!(hit.normal, Vec3::new(-1.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn intersection_lens_test() {
+        let lens = Csg::intersection(
+            sphere(Point3::new(-1.0, 0.0, 0.0), 2.0),
+            sphere(Point3::new(1.0, 0.0, 0.0), 2.0),
+        );
+        let r: Ray = Ray::new(Point3::new(-5.0, 0.0, 0.0), Vec3::new(1.0, 0.0, 0.0));
+        assert_eq!(all_hits(&lens, &r), vec![(4.0, true), (6.0, false)]);
+
+        let b: Aabb = lens.bounding_box().unwrap();
+        assert_eq!(b.min(), Point3::new(-1.0, -2.0, -2.0));
+        assert_eq!(b.max(), Point3::new(1.0, 2.0, 2.0));
+    }
+
+    #[test]
+    fn union_skips_inner_surfaces_test() {
+        let blob = Csg::union(
+            sphere(Point3::new(-1.0, 0.0, 0.0), 2.0),
+            sphere(Point3::new(1.0, 0.0, 0.0), 2.0),
+        );
+        let r: Ray = Ray::new(Point3::new(-5.0, 0.0, 0.0), Vec3::new(1.0, 0.0, 0.0));
+        assert_eq!(all_hits(&blob, &r), vec![(2.0, true), (8.0, false)]);
+    }
+
+    #[test]
+    fn ray_starting_inside_test() {
+        let origin: Point3 = Point3::new(0.0, 0.0, 0.0);
+        let shell = Csg::difference(sphere(origin, 2.0), sphere(origin, 1.0));
+        let r: Ray = Ray::new(Point3::new(1.5, 0.0, 0.0), Vec3::new(1.0, 0.0, 0.0));
+        assert_eq!(all_hits(&shell, &r), vec![(0.5, false)]);
+    }
+}
@@ -0,0 +1,80 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common;
+    use in_one_weekend::hittable::HitRecord;
+    use in_one_weekend::material::{transmittance, Dielectric, Material, RoughDielectric};
+    use in_one_weekend::ray::Ray;
+    use in_one_weekend::vec3::*;
+
+    fn record(t: f64, front_face: bool) -> HitRecord {
+        // normals face the ray, rays leaving the glass travel up
+        let normal = if front_face {
+            Vec3::new(0.0, 1.0, 0.0)
+        } else {
+            Vec3::new(0.0, -1.0, 0.0)
+        };
+        HitRecord {
+            t,
+            ..common::record(normal, front_face)
+        }
+    }
+
+    #[test]
+    fn transmittance_test() {
+        assert_eq!(
+            transmittance(Color::new(0.0, 0.0, 0.0), 10.0),
+            Color::new(1.0, 1.0, 1.0)
+        );
+        let t = transmittance(Color::new(1.0, 2.0, 0.5), 2.0);
+        assert!((t.x() - (-2.0f64).exp()).abs() < 1e-12);
+        assert!((t.y() - (-4.0f64).exp()).abs() < 1e-12);
+        assert!((t.z() - (-1.0f64).exp()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn clear_glass_test() {
+        let glass = Dielectric::new(1.5);
+        let r_in: Ray = Ray::new(Point3::new(0.0, -3.0, 0.0), Vec3::new(0.0, 1.0, 0.0));
+        let out = glass.scatter(&r_in, &record(3.0, false)).unwrap();
+        assert_eq!(out.attenuation, Color::new(1.0, 1.0, 1.0));
+    }
+
+    #[test]
+    fn absorbs_along_inside_path_test() {
+        let glass = Dielectric::with_absorption(1.5, Color::new(0.1, 0.5, 1.0));
+        // entering the glass costs nothing
+        let r_in: Ray = Ray::new(Point3::new(0.0, 1.0, 0.0), Vec3::new(0.0, -1.0, 0.0));
+        let out = glass.scatter(&r_in, &record(1.0, true)).unwrap();
+        assert_eq!(out.attenuation, Color::new(1.0, 1.0, 1.0));
+
+        // leaving it after 2 units, with a non normalized direction
+        let r_in: Ray = Ray::new(Point3::new(0.0, -2.0, 0.0), Vec3::new(0.0, 4.0, 0.0));
+        let out = glass.scatter(&r_in, &record(0.5, false)).unwrap();
+        let expected = transmittance(glass.absorption, 2.0);
+        assert!((out.attenuation - expected).length() < 1e-12);
+    }
+
+    #[test]
+    fn transmission_color_test() {
+        let color = Color::new(0.9, 0.5, 0.2);
+        let glass = Dielectric::with_transmission_color(1.5, color, 3.0);
+        let r_in: Ray = Ray::new(Point3::new(0.0, -3.0, 0.0), Vec3::new(0.0, 1.0, 0.0));
+        let out = glass.scatter(&r_in, &record(3.0, false)).unwrap();
+        assert!((out.attenuation - color).length() < 1e-9);
+
+        // twice as thick is the color squared
+        let out = glass.scatter(&r_in, &record(6.0, false)).unwrap();
+        assert!((out.attenuation - color * color).length() < 1e-9);
+    }
+
+    #[test]
+    fn rough_glass_absorbs_test() {
+        let absorption = Color::new(0.3, 0.2, 0.1);
+        let glass = RoughDielectric::with_absorption(1.5, 0.0, absorption);
+        let r_in: Ray = Ray::new(Point3::new(0.0, -1.0, 0.0), Vec3::new(0.0, 1.0, 0.0));
+        let out = glass.scatter(&r_in, &record(1.0, false)).unwrap();
+        assert!((out.attenuation - transmittance(absorption, 1.0)).length() < 1e-3);
+    }
+}
@@ -0,0 +1,90 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common;
+    use in_one_weekend::material::{Material, OrenNayar, Velvet};
+    use in_one_weekend::ray::Ray;
+    use in_one_weekend::vec3::*;
+
+    // ray arriving at the origin from `theta` degrees off the normal
+    fn incoming(theta: f64) -> Ray {
+        let t = theta.to_radians();
+        Ray::new(
+            Point3::new(-t.sin(), t.cos(), 0.0),
+            Vec3::new(t.sin(), -t.cos(), 0.0),
+        )
+    }
+
+    fn average(m: &dyn Material, r_in: &Ray, n: usize) -> Color {
+        let rec = common::record(Vec3::new(0.0, 1.0, 0.0), true);
+        let mut total = Color::new(0.0, 0.0, 0.0);
+        for _ in 0..n {
+            let out = m.scatter(r_in, &rec).unwrap();
+            assert!(out.scattered.direction().y() >= 0.0);
+            let a = out.attenuation;
+            assert!(a.x().is_finite() && a.x() >= 0.0);
+            total += a;
+        }
+        total / n as f64
+    }
+
+    #[test]
+    fn smooth_oren_nayar_is_lambertian_test() {
+        let albedo = Color::new(0.8, 0.5, 0.2);
+        let m = OrenNayar::new(albedo, 0.0);
+        let rec = common::record(Vec3::new(0.0, 1.0, 0.0), true);
+        for _ in 0..100 {
+            let out = m.scatter(&incoming(60.0), &rec).unwrap();
+            assert!((out.attenuation - albedo).length() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn oren_nayar_normal_incidence_test() {
+        // seen head on the azimuth term vanishes, leaving a * albedo
+        let m = OrenNayar::new(Color::new(1.0, 1.0, 1.0), 30.0);
+        let (a, b) = m.coefficients();
+        assert!(a < 1.0 && b > 0.0);
+        let mean = average(&m, &incoming(0.0), 1000);
+        assert!((mean.x() - a).abs() < 1e-9);
+    }
+
+    #[test]
+    fn oren_nayar_retro_reflects_test() {
+        let m = OrenNayar::new(Color::new(1.0, 1.0, 1.0), 40.0);
+        let r_in = incoming(70.0);
+        let rec = common::record(Vec3::new(0.0, 1.0, 0.0), true);
+        let (mut back, mut back_n) = (0.0, 0);
+        let (mut forward, mut forward_n) = (0.0, 0);
+        for _ in 0..20000 {
+            let out = m.scatter(&r_in, &rec).unwrap();
+            // the viewer sits toward -x
+            if out.scattered.direction().x() < 0.0 {
+                back += out.attenuation.x();
+                back_n += 1;
+            } else {
+                forward += out.attenuation.x();
+                forward_n += 1;
+            }
+        }
+        assert!(back / back_n as f64 > forward / forward_n as f64 + 0.05);
+    }
+
+    #[test]
+    fn velvet_without_sheen_is_lambertian_test() {
+        let albedo = Color::new(0.3, 0.1, 0.4);
+        let m = Velvet::new(albedo, Color::new(0.0, 0.0, 0.0), 0.5);
+        let mean = average(&m, &incoming(45.0), 1000);
+        assert!((mean - albedo).length() < 1e-12);
+    }
+
+    #[test]
+    fn velvet_sheen_grows_at_grazing_test() {
+        let m = Velvet::new(Color::new(0.0, 0.0, 0.0), Color::new(1.0, 1.0, 1.0), 0.5);
+        let head_on = average(&m, &incoming(0.0), 20000);
+        let grazing = average(&m, &incoming(80.0), 20000);
+        assert!(head_on.x() > 0.0);
+        assert!(grazing.x() > head_on.x());
+    }
+}
@@ -0,0 +1,108 @@
+#[cfg(test)]
+mod tests {
+    use in_one_weekend::aabb::Aabb;
+    use in_one_weekend::density_grid::DensityGrid;
+    use in_one_weekend::grid_medium::GridMedium;
+    use in_one_weekend::hittable::{HitRecord, Hittable};
+    use in_one_weekend::hittable_list::HittableList;
+    use in_one_weekend::instance::Instance;
+    use in_one_weekend::material::Lambertian;
+    use in_one_weekend::ray::Ray;
+    use in_one_weekend::sphere::Sphere;
+    use in_one_weekend::vec3::*;
+
+    use std::sync::Arc;
+
+    fn unit_box() -> Aabb {
+        Aabb::new(Point3::new(0.0, 0.0, 0.0), Point3::new(1.0, 1.0, 1.0))
+    }
+
+    #[test]
+    fn trilinear_density_test() {
+        // density ramps from 0 to 1 along x
+        let grid = DensityGrid::new(2, 1, 1, vec![0.0, 1.0]);
+        assert_eq!(grid.max_density(), 1.0);
+        assert_eq!(grid.density(Point3::new(0.25, 0.5, 0.5)), 0.0);
+        assert_eq!(grid.density(Point3::new(0.5, 0.5, 0.5)), 0.5);
+        assert_eq!(grid.density(Point3::new(0.9, 0.1, 0.7)), 1.0);
+    }
+
+    #[test]
+    fn vol_file_test() {
+        let mut bytes: Vec<u8> = b"2 1 2\n".to_vec();
+        for d in [0.0f32, 1.0, 2.0, 3.0] {
+            bytes.extend_from_slice(&d.to_le_bytes());
+        }
+        let path = std::env::temp_dir().join("in_one_weekend_grid_test.vol");
+        std::fs::write(&path, &bytes).unwrap();
+
+        let grid = DensityGrid::load(&path).unwrap();
+        assert_eq!((grid.nx, grid.ny, grid.nz), (2, 1, 2));
+        assert_eq!(grid.voxel(1, 0, 1), 3.0);
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(DensityGrid::from_vol_bytes(b"2 2 2\n\x00\x00").is_err());
+    }
+
+    #[test]
+    fn dense_grid_scatters_at_entry_test() {
+        let grid = Arc::new(DensityGrid::new(1, 1, 1, vec![1.0]));
+        let medium = GridMedium::with_color(grid, unit_box(), 1e9, Color::new(1.0, 1.0, 1.0));
+        let r: Ray = Ray::new(Point3::new(-1.0, 0.5, 0.5), Vec3::new(1.0, 0.0, 0.0));
+
+        let hit: HitRecord = medium.hit(&r, 0.001, f64::INFINITY).unwrap();
+        assert!((hit.t - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn empty_grid_test() {
+        let grid = Arc::new(DensityGrid::new(1, 1, 1, vec![0.0]));
+        let medium = GridMedium::with_color(grid, unit_box(), 1.0, Color::new(1.0, 1.0, 1.0));
+        let r: Ray = Ray::new(Point3::new(-1.0, 0.5, 0.5), Vec3::new(1.0, 0.0, 0.0));
+
+        assert!(medium.hit(&r, 0.001, f64::INFINITY).is_none());
+        assert_eq!(medium.transmittance(&r, 0.001, f64::INFINITY), 1.0);
+    }
+
+    #[test]
+    fn ratio_tracking_transmittance_test() {
+        // half the voxels are empty, so the ray sees an average density of 1
+        let grid = Arc::new(DensityGrid::new(4, 1, 1, vec![2.0, 0.0, 2.0, 0.0]));
+        let medium = GridMedium::with_color(grid, unit_box(), 1.0, Color::new(1.0, 1.0, 1.0));
+        let r: Ray = Ray::new(Point3::new(-1.0, 0.5, 0.5), Vec3::new(1.0, 0.0, 0.0));
+
+        let n = 20000;
+        let mean: f64 = (0..n)
+            .map(|_| medium.transmittance(&r, 0.001, f64::INFINITY))
+            .sum::<f64>()
+            / n as f64;
+        assert!((mean - (-1.0f64).exp()).abs() < 0.02, "mean {mean}");
+    }
+
+    #[test]
+    fn shadow_rays_see_media_test() {
+        // the same medium as above, moved one unit up through an instance
+        let grid = Arc::new(DensityGrid::new(4, 1, 1, vec![2.0, 0.0, 2.0, 0.0]));
+        let medium = GridMedium::with_color(grid, unit_box(), 1.0, Color::new(1.0, 1.0, 1.0));
+        let mut world: HittableList = HittableList::new();
+        world.add(Box::new(
+            Instance::new(Arc::new(medium)).translate(Vec3::new(0.0, 1.0, 0.0)),
+        ));
+        let r: Ray = Ray::new(Point3::new(-1.0, 1.5, 0.5), Vec3::new(1.0, 0.0, 0.0));
+
+        let n = 20000;
+        let mean: f64 = (0..n)
+            .map(|_| world.transmittance(&r, 0.001, f64::INFINITY))
+            .sum::<f64>()
+            / n as f64;
+        assert!((mean - (-1.0f64).exp()).abs() < 0.02, "mean {mean}");
+
+        // an opaque object anywhere along the ray blocks it
+        world.add(Box::new(Sphere::new(
+            Point3::new(3.0, 1.5, 0.5),
+            0.5,
+            Arc::new(Lambertian::new(Color::new(0.5, 0.5, 0.5))),
+        )));
+        assert_eq!(world.transmittance(&r, 0.001, f64::INFINITY), 0.0);
+    }
+}
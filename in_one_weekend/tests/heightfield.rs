@@ -0,0 +1,157 @@
+#[cfg(test)]
+mod tests {
+    use in_one_weekend::heightfield::Heightfield;
+    use in_one_weekend::hittable::{HitRecord, Hittable};
+    use in_one_weekend::image::Image;
+    use in_one_weekend::material::{Lambertian, Material};
+    use in_one_weekend::ray::Ray;
+    use in_one_weekend::vec3::*;
+
+    use std::sync::Arc;
+
+    fn material() -> Arc<dyn Material + Sync + Send> {
+        Arc::new(Lambertian::new(Color::new(0.0, 0.0, 0.0)))
+    }
+
+    fn assert_near(a: Vec3, b: Vec3) {
+        assert!((a - b).length() < 1e-6, "{a} != {b}");
+    }
+
+    #[test]
+    fn flat_terrain_test() {
+        let hf = Heightfield::from_fn(
+            33,
+            17,
+            |_, _| 0.5,
+            Point3::new(0.0, 0.0, 0.0),
+            Vec3::new(4.0, 2.0, 2.0),
+            material(),
+        );
+        let r: Ray = Ray::new(Point3::new(3.0, 5.0, 0.5), Vec3::new(0.0, -1.0, 0.0));
+
+        let hit: HitRecord = hf.hit(&r, 0.001, f64::INFINITY).unwrap();
+        assert!((hit.t - 4.0).abs() < 1e-9);
+        assert!(hit.front_face);
+        assert_near(hit.normal, Vec3::new(0.0, 1.0, 0.0));
+        assert!((hit.u - 0.75).abs() < 1e-9);
+        assert!((hit.v - 0.25).abs() < 1e-9);
+
+        let outside: Ray = Ray::new(Point3::new(5.0, 5.0, 0.5), Vec3::new(0.0, -1.0, 0.0));
+        assert!(hf.hit(&outside, 0.001, f64::INFINITY).is_none());
+    }
+
+    #[test]
+    fn slope_test() {
+        // height rises linearly with x: y = x / 2
+        let hf = Heightfield::from_fn(
+            20,
+            20,
+            |s, _| s,
+            Point3::new(0.0, 0.0, 0.0),
+            Vec3::new(2.0, 1.0, 2.0),
+            material(),
+        );
+        let r: Ray = Ray::new(Point3::new(-1.0, 0.25, 1.3), Vec3::new(1.0, 0.0, 0.0));
+
+        let hit: HitRecord = hf.hit(&r, 0.001, f64::INFINITY).unwrap();
+        assert_near(hit.p, Point3::new(0.5, 0.25, 1.3));
+        assert_near(hit.normal, unit_vector(Vec3::new(-0.5, 1.0, 0.0)));
+    }
+
+    #[test]
+    fn slope_tangents_test() {
+        let hf = Heightfield::from_fn(
+            20,
+            20,
+            |s, _| s,
+            Point3::new(0.0, 0.0, 0.0),
+            Vec3::new(2.0, 1.0, 2.0),
+            material(),
+        );
+        let r: Ray = Ray::new(Point3::new(0.7, 5.0, 1.3), Vec3::new(0.0, -1.0, 0.0));
+
+        // the tangents span the terrain over the whole field, rising with it
+        let hit: HitRecord = hf.hit(&r, 0.001, f64::INFINITY).unwrap();
+        assert_near(hit.dpdu, Vec3::new(2.0, 1.0, 0.0));
+        assert_near(hit.dpdv, Vec3::new(0.0, 0.0, 2.0));
+        assert!(dot(&hit.dpdu, &hit.normal).abs() < 1e-9);
+        assert!(dot(&hit.dpdv, &hit.normal).abs() < 1e-9);
+    }
+
+    #[test]
+    fn grazing_ray_finds_nearest_peak_test() {
+        // two ridges along z, the ray must stop at the first one
+        let hf = Heightfield::from_fn(
+            65,
+            9,
+            |s, _| {
+                if (0.24..0.26).contains(&s) || (0.74..0.76).contains(&s) {
+                    1.0
+                } else {
+                    0.0
+                }
+            },
+            Point3::new(0.0, 0.0, 0.0),
+            Vec3::new(8.0, 1.0, 1.0),
+            material(),
+        );
+        let r: Ray = Ray::new(Point3::new(-1.0, 0.5, 0.5), Vec3::new(1.0, 0.0, 0.0));
+        let hit: HitRecord = hf.hit(&r, 0.001, f64::INFINITY).unwrap();
+        assert!(hit.p.x() < 2.0 && hit.p.x() > 1.8, "{}", hit.p);
+    }
+
+    #[test]
+    fn from_image_test() {
+        // 2x2 pgm, top row bright
+        let image = Image::from_netpbm_bytes(b"P2\n# heights\n2 2\n255\n255 255\n0 0\n").unwrap();
+        assert_eq!(image.pixel(1, 0), Color::new(1.0, 1.0, 1.0));
+
+        let hf = Heightfield::from_image(
+            &image,
+            Point3::new(0.0, 0.0, 0.0),
+            Vec3::new(1.0, 1.0, 1.0),
+            material(),
+        )
+        .unwrap();
+        assert_eq!(hf.height(0, 0), 0.0);
+        assert!((hf.height(0, 1) - 1.0).abs() < 1e-9);
+
+        // a single row has no cells to build terrain from
+        let strip = Image::from_netpbm_bytes(b"P2\n2 1\n255\n0 255\n").unwrap();
+        assert!(Heightfield::from_image(
+            &strip,
+            Point3::new(0.0, 0.0, 0.0),
+            Vec3::new(1.0, 1.0, 1.0),
+            material(),
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn front_face_from_triangle_test() {
+        // a ridge along z, peaking at x = 0.5
+        let hf = Heightfield::from_fn(
+            3,
+            2,
+            |s, _| 1.0 - (2.0 * s - 1.0).abs(),
+            Point3::new(0.0, 0.0, 0.0),
+            Vec3::new(1.0, 1.0, 1.0),
+            material(),
+        );
+        // climbing more gently than the slope, the ray meets the upper
+        // side of a face whose smoothed normal already leans away from it
+        let r: Ray = Ray::new(Point3::new(0.05, 0.7, 0.5), Vec3::new(1.0, 0.5, 0.0));
+
+        let hit: HitRecord = hf.hit(&r, 0.001, f64::INFINITY).unwrap();
+        assert_near(hit.p, Point3::new(0.45, 0.9, 0.5));
+        assert!(hit.front_face);
+        assert!(dot(&r.direction(), &hit.normal) < 0.0);
+        assert_near(hit.normal, unit_vector(Vec3::new(-2.0, 1.0, 0.0)));
+
+        // from below the same face is hit on its back
+        let r: Ray = Ray::new(Point3::new(0.25, -1.0, 0.5), Vec3::new(0.0, 1.0, 0.0));
+        let hit: HitRecord = hf.hit(&r, 0.001, f64::INFINITY).unwrap();
+        assert!(!hit.front_face);
+        assert!(dot(&r.direction(), &hit.normal) < 0.0);
+    }
+}
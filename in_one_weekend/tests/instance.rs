@@ -0,0 +1,85 @@
+#[cfg(test)]
+mod tests {
+    use in_one_weekend::aabb::Aabb;
+    use in_one_weekend::hittable::{HitRecord, Hittable};
+    use in_one_weekend::instance::Instance;
+    use in_one_weekend::material::{Lambertian, Material};
+    use in_one_weekend::matrix::Mat4;
+    use in_one_weekend::ray::Ray;
+    use in_one_weekend::sphere::Sphere;
+    use in_one_weekend::vec3::*;
+
+    use std::sync::Arc;
+
+    fn unit_sphere() -> Arc<dyn Hittable + Sync + Send> {
+        let m: Arc<dyn Material + Sync + Send> =
+            Arc::new(Lambertian::new(Color::new(0.0, 0.0, 0.0)));
+        Arc::new(Sphere::new(Point3::new(0.0, 0.0, 0.0), 1.0, m))
+    }
+
+    fn assert_near(a: Vec3, b: Vec3) {
+        assert!((a - b).length() < 1e-9, "{a} != {b}");
+    }
+
+    #[test]
+    fn inverse_test() {
+        let m: Mat4 = Mat4::translation(Vec3::new(1.0, 2.0, 3.0))
+            * Mat4::rotation(Vec3::new(1.0, 1.0, 0.0), 30.0)
+            * Mat4::scaling(Vec3::new(2.0, 0.5, 4.0));
+        let p: Point3 = Point3::new(0.3, -1.2, 5.0);
+        let inv: Mat4 = m.inverse().unwrap();
+        assert_near(inv.transform_point(m.transform_point(p)), p);
+        assert!(Mat4::scaling(Vec3::new(1.0, 0.0, 1.0)).inverse().is_none());
+    }
+
+    #[test]
+    fn translated_hit_test() {
+        let inst = Instance::new(unit_sphere()).translate(Vec3::new(0.0, 0.0, -5.0));
+        let r: Ray = Ray::new(Point3::new(0.0, 0.0, 0.0), Vec3::new(0.0, 0.0, -1.0));
+
+        let hit: HitRecord = inst.hit(&r, 0.0, f64::INFINITY).unwrap();
+        assert_eq!(hit.t, 4.0);
+        assert_eq!(hit.p, Point3::new(0.0, 0.0, -4.0));
+        assert!(hit.front_face);
+        assert_near(hit.normal, Vec3::new(0.0, 0.0, 1.0));
+    }
+
+    #[test]
+    fn non_uniform_scale_normal_test() {
+        // ellipsoid stretched along x, hit on the slanted side
+        let inst = Instance::new(unit_sphere()).scale(Vec3::new(2.0, 1.0, 1.0));
+        let target: Point3 = Point3::new(-(2.0_f64.sqrt()), 0.5_f64.sqrt(), 0.0);
+        let r: Ray = Ray::new(
+            Point3::new(-5.0, 0.5_f64.sqrt(), 0.0),
+            Vec3::new(1.0, 0.0, 0.0),
+        );
+
+        let hit: HitRecord = inst.hit(&r, 0.001, f64::INFINITY).unwrap();
+        assert_near(hit.p, target);
+        // gradient of x^2/4 + y^2 - 1
+        let expected: Vec3 = unit_vector(Vec3::new(target.x() / 2.0, 2.0 * target.y(), 0.0));
+        assert!(hit.front_face);
+        assert_near(hit.normal, expected);
+    }
+
+    #[test]
+    fn bounding_box_test() {
+        let inst = Instance::new(unit_sphere())
+            .scale(Vec3::new(1.0, 2.0, 3.0))
+            .translate(Vec3::new(10.0, 0.0, 0.0));
+        assert_eq!(
+            inst.bounding_box(),
+            Some(Aabb::new(
+                Point3::new(9.0, -2.0, -3.0),
+                Point3::new(11.0, 2.0, 3.0)
+            ))
+        );
+
+        let rotated = Instance::new(unit_sphere())
+            .scale(Vec3::new(2.0, 1.0, 1.0))
+            .rotate(Vec3::new(0.0, 0.0, 1.0), 90.0);
+        let b: Aabb = rotated.bounding_box().unwrap();
+        assert_near(b.min(), Point3::new(-1.0, -2.0, -1.0));
+        assert_near(b.max(), Point3::new(1.0, 2.0, 1.0));
+    }
+}
@@ -0,0 +1,104 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::record;
+    use in_one_weekend::fresnel::fresnel_dielectric;
+    use in_one_weekend::layered::Coated;
+    use in_one_weekend::material::{Lambertian, Material};
+    use in_one_weekend::npr::Toon;
+    use in_one_weekend::ray::Ray;
+    use in_one_weekend::vec3::*;
+
+    use std::sync::Arc;
+
+    // mean attenuation and fraction of mirror reflections
+    fn average(m: &dyn Material, r_in: &Ray, n: usize) -> (Color, f64) {
+        let rec = record(Vec3::new(0.0, 1.0, 0.0), true);
+        let mirror = reflect(unit_vector(r_in.direction()), rec.normal);
+        let mut total = Color::new(0.0, 0.0, 0.0);
+        let mut mirrored = 0;
+        for _ in 0..n {
+            if let Some(out) = m.scatter(r_in, &rec) {
+                assert!(out.scattered.direction().y() > 0.0);
+                if (unit_vector(out.scattered.direction()) - mirror).length() < 1e-9 {
+                    mirrored += 1;
+                }
+                total += out.attenuation;
+            }
+        }
+        (total / n as f64, mirrored as f64 / n as f64)
+    }
+
+    #[test]
+    fn coat_reflects_by_fresnel_test() {
+        let black: Arc<dyn Material + Sync + Send> =
+            Arc::new(Lambertian::new(Color::new(0.0, 0.0, 0.0)));
+        let coated = Coated::clear(black, 1.5);
+        let r_in: Ray = Ray::new(Point3::new(-1.0, 1.0, 0.0), Vec3::new(1.0, -1.0, 0.0));
+
+        // all that comes back off a black base is the coat's reflection
+        let (mean, mirrored) = average(&coated, &r_in, 20000);
+        let expected = fresnel_dielectric(2.0f64.sqrt() / 2.0, 1.5);
+        assert!((mirrored - expected).abs() < 0.01);
+        assert!((mean.x() - expected).abs() < 0.01);
+    }
+
+    #[test]
+    fn lossless_coat_conserves_energy_test() {
+        // a white base under a clear coat reflects everything eventually
+        let white: Arc<dyn Material + Sync + Send> =
+            Arc::new(Lambertian::new(Color::new(1.0, 1.0, 1.0)));
+        let coated = Coated::clear(white, 1.5);
+        let r_in: Ray = Ray::new(Point3::new(0.0, 1.0, 1.0), Vec3::new(0.0, -1.0, -1.0));
+        let (mean, _) = average(&coated, &r_in, 20000);
+        assert!((mean - Color::new(1.0, 1.0, 1.0)).length() < 0.02, "{mean}");
+    }
+
+    #[test]
+    fn invisible_coat_test() {
+        // matching the air and absorbing nothing leaves the base alone
+        let albedo = Color::new(0.2, 0.4, 0.6);
+        let base: Arc<dyn Material + Sync + Send> = Arc::new(Lambertian::new(albedo));
+        let coated = Coated::clear(base, 1.0);
+        let r_in: Ray = Ray::new(Point3::new(0.0, 1.0, 0.0), Vec3::new(0.0, -1.0, 0.0));
+        let rec = record(Vec3::new(0.0, 1.0, 0.0), true);
+        for _ in 0..100 {
+            assert_eq!(coated.scatter(&r_in, &rec).unwrap().attenuation, albedo);
+        }
+    }
+
+    #[test]
+    fn absorbing_coat_test() {
+        // a thicker tinted coat darkens the base more
+        let white: Arc<dyn Material + Sync + Send> =
+            Arc::new(Lambertian::new(Color::new(1.0, 1.0, 1.0)));
+        let absorption = Color::new(0.5, 2.0, 4.0);
+        let thin = Coated::new(Arc::clone(&white), 1.5, 0.05, absorption);
+        let thick = Coated::new(white, 1.5, 0.2, absorption);
+        let r_in: Ray = Ray::new(Point3::new(0.0, 1.0, 0.0), Vec3::new(0.0, -1.0, 0.0));
+        let (thin_mean, _) = average(&thin, &r_in, 5000);
+        let (thick_mean, _) = average(&thick, &r_in, 5000);
+        for a in 0..3 {
+            assert!(thick_mean[a] < thin_mean[a]);
+        }
+        // blue is absorbed the most
+        assert!(thin_mean.z() < thin_mean.x());
+    }
+
+    #[test]
+    fn coat_keeps_base_emission_test() {
+        // a self lit toon base still shows through its coat
+        let toon = Toon::new(
+            Color::new(1.0, 1.0, 1.0),
+            Color::new(0.0, 0.0, 0.0),
+            Vec3::new(0.0, 1.0, 0.0),
+            3,
+        );
+        let rec = record(Vec3::new(0.0, 1.0, 0.0), true);
+        let lit = toon.emitted(&rec);
+        assert_eq!(lit, Color::new(1.0, 1.0, 1.0));
+        let coated = Coated::clear(Arc::new(toon), 1.5);
+        assert_eq!(coated.emitted(&rec), lit);
+    }
+}
@@ -0,0 +1,117 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::record;
+    use in_one_weekend::fresnel::*;
+    use in_one_weekend::material::{Material, RoughConductor, RoughDielectric};
+    use in_one_weekend::microfacet::TrowbridgeReitz;
+    use in_one_weekend::ray::Ray;
+    use in_one_weekend::vec3::*;
+
+    use std::f64::consts::PI;
+
+    #[test]
+    fn fresnel_test() {
+        assert!((fresnel_dielectric(1.0, 1.5) - 0.04).abs() < 1e-12);
+        // past the critical angle inside glass
+        assert_eq!(fresnel_dielectric(0.2, 1.0 / 1.5), 1.0);
+        // grazing incidence reflects everything
+        assert!((fresnel_dielectric(0.0, 1.5) - 1.0).abs() < 1e-12);
+        assert_eq!(fresnel_schlick(1.0, 0.5), 0.5);
+        assert_eq!(fresnel_schlick(0.0, 0.5), 1.0);
+    }
+
+    #[test]
+    fn distribution_normalized_test() {
+        // the projected microfacet area must equal the macro surface area
+        let d = TrowbridgeReitz::new(0.3, 0.6);
+        let n = 800;
+        let mut integral = 0.0;
+        for i in 0..n {
+            let theta = (i as f64 + 0.5) / n as f64 * PI / 2.0;
+            for j in 0..n {
+                let phi = (j as f64 + 0.5) / n as f64 * 2.0 * PI;
+                let wm = Vec3::new(
+                    theta.sin() * phi.cos(),
+                    theta.sin() * phi.sin(),
+                    theta.cos(),
+                );
+                let d_omega = theta.sin() * (PI / 2.0 / n as f64) * (2.0 * PI / n as f64);
+                integral += d.d(wm) * wm.z() * d_omega;
+            }
+        }
+        assert!((integral - 1.0).abs() < 1e-2, "{integral}");
+    }
+
+    #[test]
+    fn visible_normals_test() {
+        let d = TrowbridgeReitz::from_roughness(0.7);
+        let wo = unit_vector(Vec3::new(0.8, 0.1, 0.3));
+        for i in 0..10 {
+            for j in 0..10 {
+                let wm = d.sample_wm(wo, i as f64 / 10.0, j as f64 / 10.0);
+                assert!(wm.z() > 0.0);
+                assert!(dot(&wo, &wm) >= -1e-9);
+                assert!((wm.length() - 1.0).abs() < 1e-9);
+            }
+        }
+    }
+
+    #[test]
+    fn rough_materials_scatter_test() {
+        // masking only ever takes energy away, and light stays on the
+        // side it was reflected or transmitted to
+        let metal = RoughConductor::new(Color::new(1.0, 1.0, 1.0), 0.5);
+        let glass = RoughDielectric::new(1.5, 0.5);
+        let rec = record(Vec3::new(0.0, 1.0, 0.0), true);
+        let r_in: Ray = Ray::new(Point3::new(-1.0, 1.0, 0.0), Vec3::new(1.0, -1.0, 0.0));
+        for _ in 0..1000 {
+            if let Some(out) = metal.scatter(&r_in, &rec) {
+                assert!(out.scattered.direction().y() > 0.0);
+                assert!(out.attenuation.x() > 0.0 && out.attenuation.x() <= 1.0);
+            }
+            if let Some(out) = glass.scatter(&r_in, &rec) {
+                assert!(out.scattered.direction().y() != 0.0);
+                assert!(out.attenuation.x() > 0.0 && out.attenuation.x() <= 1.0);
+            }
+        }
+    }
+
+    #[test]
+    fn smooth_conductor_is_a_mirror_test() {
+        // at zero roughness every sampled microfacet is the macro normal,
+        // so light leaves in the mirror direction with nothing masked.
+        // the grid stays clear of the disk's rim, where the sample is
+        // projected onto the very edge of the hemisphere
+        let d = TrowbridgeReitz::from_roughness(0.0);
+        let wo = unit_vector(Vec3::new(-1.0, 0.0, 1.0));
+        let expected = unit_vector(Vec3::new(1.0, 0.0, 1.0));
+        for i in 0..10 {
+            for j in 0..10 {
+                let wm = d.sample_wm(wo, i as f64 / 10.0, j as f64 / 10.0);
+                let wi = reflect(-wo, wm);
+                assert!((wi - expected).length() < 1e-3, "{wi}");
+                assert!((d.g(wo, wi) / d.g1(wo) - 1.0).abs() < 1e-3);
+            }
+        }
+    }
+
+    #[test]
+    fn smooth_dielectric_refracts_test() {
+        // 4% reflect straight back, the rest passes straight through
+        let d = TrowbridgeReitz::from_roughness(0.0);
+        let wo = Vec3::new(0.0, 0.0, 1.0);
+        for i in 0..10 {
+            for j in 0..10 {
+                let wm = d.sample_wm(wo, i as f64 / 10.0, j as f64 / 10.0);
+                assert!((fresnel_dielectric(dot(&wo, &wm), 1.5) - 0.04).abs() < 1e-3);
+                let reflected = reflect(-wo, wm);
+                let refracted = refract(-wo, wm, 1.0 / 1.5);
+                assert!((reflected - wo).length() < 1e-3, "{reflected}");
+                assert!((refracted + wo).length() < 1e-3, "{refracted}");
+                assert!((d.g(wo, refracted) / d.g1(wo) - 1.0).abs() < 1e-3);
+            }
+        }
+    }
+}
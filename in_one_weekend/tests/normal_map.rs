@@ -0,0 +1,127 @@
+#[cfg(test)]
+mod tests {
+    use in_one_weekend::hittable::Hittable;
+    use in_one_weekend::instance::Instance;
+    use in_one_weekend::material::{Material, Metal};
+    use in_one_weekend::normal_map::{BumpMap, NormalMap};
+    use in_one_weekend::plane::Plane;
+    use in_one_weekend::ray::Ray;
+    use in_one_weekend::sphere::Sphere;
+    use in_one_weekend::texture::{SolidColor, Texture};
+    use in_one_weekend::vec3::*;
+
+    use std::sync::Arc;
+
+    // height rising along u
+    struct Ramp;
+
+    impl Texture for Ramp {
+        fn value(&self, u: f64, _v: f64, _p: &Point3) -> Color {
+            Color::new(u, u, u)
+        }
+    }
+
+    fn mirror() -> Arc<dyn Material + Sync + Send> {
+        Arc::new(Metal::new(Color::new(1.0, 1.0, 1.0), 0.0))
+    }
+
+    #[test]
+    fn sphere_tangents_test() {
+        // moving along dpdu and dpdv changes u and v by the same step
+        let p = unit_vector(Vec3::new(0.3, -0.4, 0.8));
+        let (u, v) = Sphere::get_sphere_uv(p);
+        let (dpdu, dpdv) = Sphere::get_sphere_tangents(p, 2.0);
+        let eps = 1e-6;
+        let (u1, v1) = Sphere::get_sphere_uv(unit_vector(p + eps * dpdu / 2.0));
+        assert!((u1 - u - eps).abs() < 1e-9 && (v1 - v).abs() < 1e-9);
+        let (u2, v2) = Sphere::get_sphere_uv(unit_vector(p + eps * dpdv / 2.0));
+        assert!((u2 - u).abs() < 1e-9 && (v2 - v - eps).abs() < 1e-9);
+    }
+
+    #[test]
+    fn tangent_frame_test() {
+        let sphere = Sphere::new(Point3::new(0.0, 0.0, 0.0), 1.0, mirror());
+        let r: Ray = Ray::new(Point3::new(0.2, 0.3, 5.0), Vec3::new(0.0, 0.0, -1.0));
+        let rec = sphere.hit(&r, 0.001, f64::INFINITY).unwrap();
+        let frame = rec.tangent_frame();
+        assert!((frame.w() - rec.normal).length() < 1e-12);
+        assert!(dot(&frame.u(), &frame.w()).abs() < 1e-12);
+        assert!(dot(&frame.u(), &rec.dpdu) > 0.0);
+        assert!(dot(&frame.v(), &rec.dpdv) > 0.0);
+
+        // instances carry their tangents through the transform
+        let turned = Instance::new(Arc::new(Plane::new(
+            Point3::new(0.0, 0.0, 0.0),
+            Vec3::new(0.0, 1.0, 0.0),
+            mirror(),
+        )))
+        .rotate(Vec3::new(0.0, 0.0, 1.0), 90.0);
+        let r: Ray = Ray::new(Point3::new(-1.0, 0.5, 0.5), Vec3::new(1.0, 0.0, 0.0));
+        let rec = turned.hit(&r, 0.001, f64::INFINITY).unwrap();
+        assert!(dot(&rec.dpdu, &rec.normal).abs() < 1e-9);
+        assert!(dot(&rec.dpdv, &rec.normal).abs() < 1e-9);
+    }
+
+    #[test]
+    fn flat_maps_test() {
+        let plane = Plane::new(
+            Point3::new(0.0, 0.0, 0.0),
+            Vec3::new(0.0, 1.0, 0.0),
+            mirror(),
+        );
+        let r: Ray = Ray::new(Point3::new(0.2, 1.0, 0.3), Vec3::new(0.0, -1.0, 0.0));
+        let rec = plane.hit(&r, 0.001, f64::INFINITY).unwrap();
+
+        let flat = NormalMap::new(
+            mirror(),
+            Arc::new(SolidColor::new(Color::new(0.5, 0.5, 1.0))),
+            1.0,
+        );
+        assert!((flat.shading_normal(&rec) - rec.normal).length() < 1e-12);
+        let level = BumpMap::new(
+            mirror(),
+            Arc::new(SolidColor::new(Color::new(0.7, 0.7, 0.7))),
+            1.0,
+        );
+        assert!((level.shading_normal(&rec) - rec.normal).length() < 1e-9);
+    }
+
+    #[test]
+    fn tilted_maps_test() {
+        let plane = Plane::new(
+            Point3::new(0.0, 0.0, 0.0),
+            Vec3::new(0.0, 1.0, 0.0),
+            mirror(),
+        );
+        let r: Ray = Ray::new(Point3::new(0.2, 1.0, 0.3), Vec3::new(0.0, -1.0, 0.0));
+        let rec = plane.hit(&r, 0.001, f64::INFINITY).unwrap();
+
+        // leaning towards +u in tangent space
+        let tilted = NormalMap::new(
+            mirror(),
+            Arc::new(SolidColor::new(Color::new(0.8, 0.5, 0.9))),
+            1.0,
+        );
+        let n = tilted.shading_normal(&rec);
+        assert!(dot(&n, &rec.dpdu) > 0.1);
+        let none = NormalMap::new(
+            mirror(),
+            Arc::new(SolidColor::new(Color::new(0.8, 0.5, 0.9))),
+            0.0,
+        );
+        assert!((none.shading_normal(&rec) - rec.normal).length() < 1e-12);
+
+        // a slope rising along u faces back down it
+        let bumped = BumpMap::new(mirror(), Arc::new(Ramp), 0.5);
+        let n = bumped.shading_normal(&rec);
+        assert!(dot(&n, &rec.dpdu) < -0.1);
+        assert!(dot(&n, &rec.normal) > 0.0);
+
+        // the mirror reflects about the shading normal from the hit point
+        let out = bumped.scatter(&r, &rec).unwrap();
+        let expected = reflect(unit_vector(r.direction()), n);
+        assert!((unit_vector(out.scattered.direction()) - expected).length() < 1e-9);
+        assert_eq!(out.scattered.origin(), rec.p);
+        assert!(rec.front_face);
+    }
+}
@@ -0,0 +1,130 @@
+#[cfg(test)]
+mod tests {
+    use in_one_weekend::camera::PerspectiveCamera;
+    use in_one_weekend::hittable_list::HittableList;
+    use in_one_weekend::material::{Lambertian, Material};
+    use in_one_weekend::npr::{first_hit, GBuffer, Outline, Toon};
+    use in_one_weekend::ray::Ray;
+    use in_one_weekend::scenes::{ray_color, ImageSettings, SceneSettings};
+    use in_one_weekend::sphere::Sphere;
+    use in_one_weekend::vec3::*;
+
+    use std::sync::Arc;
+
+    fn toon() -> Toon {
+        Toon::new(
+            Color::new(1.0, 1.0, 1.0),
+            Color::new(0.0, 0.0, 0.0),
+            Vec3::new(0.0, 1.0, 0.0),
+            3,
+        )
+    }
+
+    // a sphere filling the middle of a small square image, looked at
+    // from +z
+    fn sphere_scene() -> SceneSettings {
+        let mut world: HittableList = HittableList::new();
+        let m: Arc<dyn Material + Sync + Send> =
+            Arc::new(Lambertian::new(Color::new(0.5, 0.5, 0.5)));
+        world.add(Box::new(Sphere::new(Point3::new(0.0, 0.0, 0.0), 1.0, m)));
+        let cam = PerspectiveCamera::new(
+            Point3::new(0.0, 0.0, 5.0),
+            Point3::new(0.0, 0.0, 0.0),
+            Vec3::new(0.0, 1.0, 0.0),
+            40.0,
+            1.0,
+            0.0,
+            5.0,
+        );
+        SceneSettings {
+            world,
+            cam: Box::new(cam),
+            image_settings: ImageSettings {
+                aspect_ratio: 1.0,
+                image_width: 41,
+                image_height: 41,
+                samples_per_pixel: 1,
+                max_depth: 10,
+                spectral: false,
+            },
+        }
+    }
+
+    #[test]
+    fn toon_quantizes_lighting_test() {
+        let m = toon();
+        assert_eq!(m.shade(Vec3::new(0.0, 1.0, 0.0)), Color::new(1.0, 1.0, 1.0));
+        assert_eq!(m.shade(Vec3::new(1.0, 0.0, 0.0)), Color::new(0.0, 0.0, 0.0));
+        // intensities between a third and two thirds share the middle band
+        let middle = Color::new(0.5, 0.5, 0.5);
+        assert_eq!(m.shade(Vec3::new(2.0, 1.0, 0.0)), middle);
+        assert_eq!(m.shade(Vec3::new(1.5, 1.0, 0.0)), middle);
+
+        let flat = Toon::new(
+            Color::new(0.2, 0.4, 0.6),
+            Color::new(0.0, 0.0, 0.0),
+            Vec3::new(0.0, 1.0, 0.0),
+            1,
+        );
+        assert_eq!(flat.shade(Vec3::new(1.0, 0.0, 0.0)), flat.lit);
+    }
+
+    #[test]
+    fn toon_is_self_lit_test() {
+        let mut world: HittableList = HittableList::new();
+        world.add(Box::new(Sphere::new(
+            Point3::new(0.0, 0.0, 0.0),
+            1.0,
+            Arc::new(toon()),
+        )));
+        // straight down onto the top of the sphere, facing the light
+        let r: Ray = Ray::new(Point3::new(0.0, 5.0, 0.0), Vec3::new(0.0, -1.0, 0.0));
+        let c = ray_color(&r, &world, 10);
+        assert!((c - Color::new(1.0, 1.0, 1.0)).length() < 1e-9);
+    }
+
+    #[test]
+    fn first_hit_reports_object_test() {
+        let mut scene = sphere_scene();
+        let m: Arc<dyn Material + Sync + Send> =
+            Arc::new(Lambertian::new(Color::new(0.5, 0.5, 0.5)));
+        scene
+            .world
+            .add(Box::new(Sphere::new(Point3::new(0.0, 0.0, 2.0), 0.5, m)));
+        let r: Ray = Ray::new(Point3::new(0.0, 0.0, 5.0), Vec3::new(0.0, 0.0, -1.0));
+        let (idx, rec) = first_hit(&scene.world, &r).unwrap();
+        assert_eq!(idx, 1);
+        assert!((rec.t - 2.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn outline_follows_silhouette_test() {
+        let scene = sphere_scene();
+        let gbuffer = GBuffer::new(&scene);
+        assert_eq!(gbuffer.get(20, 20).object, Some(0));
+        assert_eq!(gbuffer.get(0, 0).object, None);
+
+        let edges = gbuffer.edges(&Outline::default());
+        let at = |i: i32, j: i32| edges[(j * 41 + i) as usize];
+        assert!(!at(20, 20));
+        assert!(!at(0, 0));
+        // the middle row crosses the silhouette twice, and nothing inside
+        // the sphere bends past the crease angle between neighbors
+        let crossings = (0..41).filter(|&i| at(i, 20)).count();
+        assert_eq!(crossings, 2);
+
+        // thicker lines cover more pixels, none draws nothing
+        let thick = gbuffer.edges(&Outline::new(Color::new(0.0, 0.0, 0.0), 45.0, 2));
+        assert!(thick.iter().filter(|&&e| e).count() > edges.iter().filter(|&&e| e).count());
+        let none = gbuffer.edges(&Outline::new(Color::new(0.0, 0.0, 0.0), 45.0, 0));
+        assert!(none.iter().all(|&e| !e));
+    }
+
+    #[test]
+    fn outline_marks_creases_test() {
+        // with a tiny crease angle the curving sphere itself is outlined
+        let gbuffer = GBuffer::new(&sphere_scene());
+        let creases = gbuffer.edges(&Outline::new(Color::new(0.0, 0.0, 0.0), 0.1, 1));
+        assert!(creases[(20 * 41 + 20) as usize]);
+    }
+}
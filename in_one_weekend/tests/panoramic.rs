@@ -0,0 +1,133 @@
+#[cfg(test)]
+mod tests {
+    use in_one_weekend::camera::Camera;
+    use in_one_weekend::panoramic::*;
+    use in_one_weekend::vec3::*;
+
+    fn close(a: Vec3, b: Vec3) -> bool {
+        (unit_vector(a) - unit_vector(b)).length() < 1e-9
+    }
+
+    // looking down -z from the origin, +x to the right and +y up
+    fn equirect() -> EquirectangularCamera {
+        EquirectangularCamera::new(
+            Point3::new(0.0, 0.0, 0.0),
+            Point3::new(0.0, 0.0, -1.0),
+            Vec3::new(0.0, 1.0, 0.0),
+        )
+    }
+
+    fn fisheye(fov: f64, projection: FisheyeProjection) -> FisheyeCamera {
+        FisheyeCamera::new(
+            Point3::new(0.0, 0.0, 0.0),
+            Point3::new(0.0, 0.0, -1.0),
+            Vec3::new(0.0, 1.0, 0.0),
+            fov,
+            1.0,
+            projection,
+        )
+    }
+
+    #[test]
+    fn equirectangular_covers_sphere_test() {
+        let cam = equirect();
+        assert!(close(
+            cam.get_ray(0.5, 0.5).direction(),
+            Vec3::new(0.0, 0.0, -1.0)
+        ));
+        assert!(close(
+            cam.get_ray(0.75, 0.5).direction(),
+            Vec3::new(1.0, 0.0, 0.0)
+        ));
+        assert!(close(
+            cam.get_ray(0.25, 0.5).direction(),
+            Vec3::new(-1.0, 0.0, 0.0)
+        ));
+        assert!(close(
+            cam.get_ray(0.0, 0.5).direction(),
+            Vec3::new(0.0, 0.0, 1.0)
+        ));
+        assert!(close(
+            cam.get_ray(0.3, 1.0).direction(),
+            Vec3::new(0.0, 1.0, 0.0)
+        ));
+        assert!(close(
+            cam.get_ray(0.8, 0.0).direction(),
+            Vec3::new(0.0, -1.0, 0.0)
+        ));
+        // the left and right edges meet behind the camera
+        assert!(close(
+            cam.get_ray(0.0, 0.3).direction(),
+            cam.get_ray(1.0, 0.3).direction()
+        ));
+    }
+
+    #[test]
+    fn fisheye_image_circle_test() {
+        for projection in [FisheyeProjection::Equidistant, FisheyeProjection::Equisolid] {
+            let cam = fisheye(180.0, projection);
+            assert!(close(
+                cam.get_ray(0.5, 0.5).direction(),
+                Vec3::new(0.0, 0.0, -1.0)
+            ));
+            // the rim of the circle is fov / 2 off axis
+            assert!(close(
+                cam.get_ray(1.0, 0.5).direction(),
+                Vec3::new(1.0, 0.0, 0.0)
+            ));
+            assert!(close(
+                cam.get_ray(0.5, 1.0).direction(),
+                Vec3::new(0.0, 1.0, 0.0)
+            ));
+            assert!(cam.in_frame(0.5, 0.5));
+            assert!(cam.in_frame(1.0, 0.5));
+            assert!(!cam.in_frame(0.0, 0.0));
+            assert!(!cam.in_frame(0.95, 0.95));
+        }
+    }
+
+    #[test]
+    fn fisheye_projections_test() {
+        let equidistant = fisheye(180.0, FisheyeProjection::Equidistant);
+        let equisolid = fisheye(180.0, FisheyeProjection::Equisolid);
+        let half_pi = std::f64::consts::FRAC_PI_2;
+        assert!((equidistant.theta(0.5) - half_pi / 2.0).abs() < 1e-12);
+        // equisolid squeezes the center less and the rim more
+        let expected = 2.0 * (0.5 * (half_pi / 2.0).sin()).asin();
+        assert!((equisolid.theta(0.5) - expected).abs() < 1e-12);
+        assert!(equisolid.theta(0.5) < equidistant.theta(0.5));
+
+        // a full 360 degree fisheye sees straight back at its rim
+        let full = fisheye(360.0, FisheyeProjection::Equidistant);
+        assert!(close(
+            full.get_ray(1.0, 0.5).direction(),
+            Vec3::new(0.0, 0.0, 1.0)
+        ));
+    }
+
+    #[test]
+    fn cube_map_faces_test() {
+        let cam = CubeMapCamera::new(Point3::new(1.0, 2.0, 3.0));
+        let axes = [
+            Vec3::new(1.0, 0.0, 0.0),
+            Vec3::new(-1.0, 0.0, 0.0),
+            Vec3::new(0.0, 1.0, 0.0),
+            Vec3::new(0.0, -1.0, 0.0),
+            Vec3::new(0.0, 0.0, 1.0),
+            Vec3::new(0.0, 0.0, -1.0),
+        ];
+        for (face, axis) in axes.iter().enumerate() {
+            let r = cam.get_ray((face as f64 + 0.5) / 6.0, 0.5);
+            assert_eq!(r.origin(), Point3::new(1.0, 2.0, 3.0));
+            assert!(close(r.direction(), *axis));
+        }
+        assert_eq!(CUBE_MAP_ASPECT_RATIO, 6.0);
+
+        // every face spans 90 degrees and keeps +y up on the side faces
+        let corner = cam.get_ray(0.0, 1.0).direction();
+        assert!(close(corner, Vec3::new(1.0, 1.0, 1.0)));
+        for face in [0, 1, 4, 5] {
+            assert!(CubeMapCamera::face_direction(face, 0.0, 1.0).y() > 0.0);
+        }
+    }
+}
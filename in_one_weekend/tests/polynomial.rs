@@ -0,0 +1,46 @@
+#[cfg(test)]
+mod tests {
+    use in_one_weekend::polynomial::*;
+
+    fn sorted(mut v: Vec<f64>) -> Vec<f64> {
+        v.sort_by(|a, b| a.total_cmp(b));
+        v
+    }
+
+    fn assert_roots(actual: Vec<f64>, expected: &[f64]) {
+        let actual = sorted(actual);
+        assert_eq!(actual.len(), expected.len(), "{actual:?}");
+        for (a, e) in actual.iter().zip(expected) {
+            assert!((a - e).abs() < 1e-6, "{actual:?} != {expected:?}");
+        }
+    }
+
+    #[test]
+    fn quadratic_test() {
+        // (x - 1)(x - 3)
+        assert_roots(solve_quadratic(1.0, -4.0, 3.0), &[1.0, 3.0]);
+        assert_roots(solve_quadratic(1.0, 0.0, 1.0), &[]);
+        assert_roots(solve_quadratic(0.0, 2.0, -4.0), &[2.0]);
+    }
+
+    #[test]
+    fn cubic_test() {
+        // (x + 2)(x - 1)(x - 4)
+        assert_roots(solve_cubic(1.0, -3.0, -6.0, 8.0), &[-2.0, 1.0, 4.0]);
+        // x^3 - 8
+        assert_roots(solve_cubic(2.0, 0.0, 0.0, -16.0), &[2.0]);
+    }
+
+    #[test]
+    fn quartic_test() {
+        // (x - 1)(x - 2)(x - 3)(x - 4)
+        assert_roots(
+            solve_quartic(1.0, -10.0, 35.0, -50.0, 24.0),
+            &[1.0, 2.0, 3.0, 4.0],
+        );
+        // (x^2 + 1)(x^2 - 9)
+        assert_roots(solve_quartic(1.0, 0.0, -8.0, 0.0, -9.0), &[-3.0, 3.0]);
+        // x^4 + 1
+        assert_roots(solve_quartic(1.0, 0.0, 0.0, 0.0, 1.0), &[]);
+    }
+}
@@ -0,0 +1,123 @@
+#[cfg(test)]
+mod tests {
+    use in_one_weekend::cone::Cone;
+    use in_one_weekend::cylinder::Cylinder;
+    use in_one_weekend::disk::Disk;
+    use in_one_weekend::hittable::{HitRecord, Hittable};
+    use in_one_weekend::material::{Lambertian, Material};
+    use in_one_weekend::plane::Plane;
+    use in_one_weekend::ray::Ray;
+    use in_one_weekend::torus::Torus;
+    use in_one_weekend::vec3::*;
+
+    use std::sync::Arc;
+
+    fn material() -> Arc<dyn Material + Sync + Send> {
+        Arc::new(Lambertian::new(Color::new(0.0, 0.0, 0.0)))
+    }
+
+    fn assert_near(a: Vec3, b: Vec3) {
+        assert!((a - b).length() < 1e-6, "{a} != {b}");
+    }
+
+    #[test]
+    fn plane_test() {
+        let plane = Plane::new(
+            Point3::new(0.0, -1.0, 0.0),
+            Vec3::new(0.0, 2.0, 0.0),
+            material(),
+        );
+        let r: Ray = Ray::new(Point3::new(0.5, 1.0, 0.25), Vec3::new(0.0, -1.0, 0.0));
+
+        let hit: HitRecord = plane.hit(&r, 0.0, f64::INFINITY).unwrap();
+        assert_eq!(hit.t, 2.0);
+        assert!(hit.front_face);
+        assert_eq!(hit.normal, Vec3::new(0.0, 1.0, 0.0));
+        assert!((0.0..1.0).contains(&hit.u) && (0.0..1.0).contains(&hit.v));
+
+        let parallel: Ray = Ray::new(Point3::new(0.0, 1.0, 0.0), Vec3::new(1.0, 0.0, 0.0));
+        assert!(plane.hit(&parallel, 0.0, f64::INFINITY).is_none());
+    }
+
+    #[test]
+    fn disk_test() {
+        let disk = Disk::new(
+            Point3::new(0.0, 0.0, 0.0),
+            Vec3::new(0.0, 0.0, 1.0),
+            1.0,
+            material(),
+        );
+        let inside: Ray = Ray::new(Point3::new(0.5, 0.0, -2.0), Vec3::new(0.0, 0.0, 1.0));
+        let hit: HitRecord = disk.hit(&inside, 0.0, f64::INFINITY).unwrap();
+        assert!(!hit.front_face);
+        assert_eq!(hit.normal, Vec3::new(0.0, 0.0, -1.0));
+        assert!((hit.v - 0.5).abs() < 1e-9);
+
+        let outside: Ray = Ray::new(Point3::new(1.5, 0.0, -2.0), Vec3::new(0.0, 0.0, 1.0));
+        assert!(disk.hit(&outside, 0.0, f64::INFINITY).is_none());
+    }
+
+    #[test]
+    fn cylinder_test() {
+        let capped = Cylinder::new(Point3::new(0.0, 0.0, 0.0), 1.0, 2.0, true, material());
+        let side: Ray = Ray::new(Point3::new(-5.0, 1.0, 0.0), Vec3::new(1.0, 0.0, 0.0));
+        let hit: HitRecord = capped.hit(&side, 0.0, f64::INFINITY).unwrap();
+        assert_eq!(hit.t, 4.0);
+        assert_near(hit.normal, Vec3::new(-1.0, 0.0, 0.0));
+        assert!((hit.v - 0.5).abs() < 1e-9);
+
+        let top: Ray = Ray::new(Point3::new(0.5, 5.0, 0.0), Vec3::new(0.0, -1.0, 0.0));
+        let hit: HitRecord = capped.hit(&top, 0.0, f64::INFINITY).unwrap();
+        assert_eq!(hit.t, 3.0);
+        assert_eq!(hit.normal, Vec3::new(0.0, 1.0, 0.0));
+
+        // without caps the ray passes down the tube and hits nothing
+        let open = Cylinder::new(Point3::new(0.0, 0.0, 0.0), 1.0, 2.0, false, material());
+        assert!(open.hit(&top, 0.0, f64::INFINITY).is_none());
+    }
+
+    #[test]
+    fn cone_test() {
+        let cone = Cone::new(Point3::new(0.0, 0.0, 0.0), 1.0, 1.0, true, material());
+        // halfway up the side is at radius 0.5
+        let side: Ray = Ray::new(Point3::new(-5.0, 0.5, 0.0), Vec3::new(1.0, 0.0, 0.0));
+        let hit: HitRecord = cone.hit(&side, 0.0, f64::INFINITY).unwrap();
+        assert!((hit.t - 4.5).abs() < 1e-9);
+        assert_near(hit.normal, unit_vector(Vec3::new(-1.0, 1.0, 0.0)));
+
+        let bottom: Ray = Ray::new(Point3::new(0.2, -1.0, 0.0), Vec3::new(0.0, 1.0, 0.0));
+        let hit: HitRecord = cone.hit(&bottom, 0.0, f64::INFINITY).unwrap();
+        assert_eq!(hit.t, 1.0);
+        assert_eq!(hit.normal, Vec3::new(0.0, -1.0, 0.0));
+
+        // above the apex
+        let above: Ray = Ray::new(Point3::new(-5.0, 1.5, 0.0), Vec3::new(1.0, 0.0, 0.0));
+        assert!(cone.hit(&above, 0.0, f64::INFINITY).is_none());
+    }
+
+    #[test]
+    fn torus_test() {
+        let torus = Torus::new(Point3::new(0.0, 0.0, 0.0), 2.0, 0.5, material());
+        let r: Ray = Ray::new(Point3::new(-5.0, 0.0, 0.0), Vec3::new(2.0, 0.0, 0.0));
+
+        let hit: HitRecord = torus.hit(&r, 0.0, f64::INFINITY).unwrap();
+        assert!((hit.t - 1.25).abs() < 1e-9);
+        assert_near(hit.p, Point3::new(-2.5, 0.0, 0.0));
+        assert_near(hit.normal, Vec3::new(-1.0, 0.0, 0.0));
+
+        // through the hole from the inner wall
+        let hit: HitRecord = torus.hit(&r, 1.5, f64::INFINITY).unwrap();
+        assert_near(hit.p, Point3::new(-1.5, 0.0, 0.0));
+        assert!(!hit.front_face);
+
+        // straight down the hole
+        let hole: Ray = Ray::new(Point3::new(0.0, 5.0, 0.0), Vec3::new(0.0, -1.0, 0.0));
+        assert!(torus.hit(&hole, 0.0, f64::INFINITY).is_none());
+
+        // from above onto the top of the tube
+        let top: Ray = Ray::new(Point3::new(0.0, 5.0, 2.0), Vec3::new(0.0, -1.0, 0.0));
+        let hit: HitRecord = torus.hit(&top, 0.0, f64::INFINITY).unwrap();
+        assert!((hit.t - 4.5).abs() < 1e-9);
+        assert_near(hit.normal, Vec3::new(0.0, 1.0, 0.0));
+    }
+}
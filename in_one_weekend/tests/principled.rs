@@ -0,0 +1,103 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::record;
+    use in_one_weekend::fresnel::fresnel_schlick;
+    use in_one_weekend::material::Material;
+    use in_one_weekend::principled::{Lobe, Principled};
+    use in_one_weekend::ray::Ray;
+    use in_one_weekend::vec3::*;
+
+    fn probability(m: &Principled, lobe: Lobe) -> f64 {
+        m.lobe_probabilities()
+            .iter()
+            .find(|(l, _)| *l == lobe)
+            .unwrap()
+            .1
+    }
+
+    #[test]
+    fn smooth_metal_is_a_mirror_test() {
+        let gold = Color::new(1.0, 0.8, 0.3);
+        let metal = Principled {
+            metallic: 1.0,
+            roughness: 0.0,
+            ..Principled::new(gold)
+        };
+        let up = Vec3::new(0.0, 0.0, 1.0);
+
+        // only the specular lobe is ever picked, with the base color at
+        // normal incidence
+        assert_eq!(probability(&metal, Lobe::Specular), 1.0);
+        assert!((metal.eval_specular(up, up) - gold).length() < 1e-3);
+        assert!(metal.eval_diffuse(up, up).near_zero());
+    }
+
+    #[test]
+    fn smooth_glass_transmits_test() {
+        let glass = Principled {
+            roughness: 0.0,
+            transmission: 1.0,
+            ..Principled::new(Color::new(1.0, 1.0, 1.0))
+        };
+        let up = Vec3::new(0.0, 0.0, 1.0);
+
+        // at normal incidence 4% is reflected and the rest transmitted,
+        // so averaged over the lobes the weights add up to one
+        assert_eq!(probability(&glass, Lobe::Diffuse), 0.0);
+        assert_eq!(probability(&glass, Lobe::Clearcoat), 0.0);
+        let reflected = glass.eval_specular(up, up);
+        let transmitted = glass.eval_transmission(up, up);
+        assert!((reflected.x() - 0.04).abs() < 1e-3, "{reflected}");
+        assert!((transmitted.x() - 0.96).abs() < 1e-3, "{transmitted}");
+    }
+
+    #[test]
+    fn diffuse_gets_what_specular_leaves_test() {
+        let white = Color::new(1.0, 1.0, 1.0);
+        let matte = Principled {
+            specular: 0.0,
+            ..Principled::new(white)
+        };
+        let shiny = Principled {
+            specular: 1.0,
+            ..Principled::new(white)
+        };
+        let wo = unit_vector(Vec3::new(1.0, 0.0, 0.2));
+        let wi = unit_vector(Vec3::new(-0.3, 0.2, 1.0));
+
+        // even without a specular slider schlick reflects at grazing angles
+        let expected = (1.0 - fresnel_schlick(wo.z(), 0.08)) / (1.0 - fresnel_schlick(wo.z(), 0.0));
+        let ratio = shiny.eval_diffuse(wo, wi).x() / matte.eval_diffuse(wo, wi).x();
+        assert!((ratio - expected).abs() < 1e-12, "{ratio}");
+
+        // metals have no diffuse lobe at all
+        let metal = Principled {
+            metallic: 1.0,
+            ..Principled::new(white)
+        };
+        assert!(metal.eval_diffuse(wo, wi).near_zero());
+        assert_eq!(probability(&metal, Lobe::Diffuse), 0.0);
+    }
+
+    #[test]
+    fn diffuse_stays_above_surface_test() {
+        let m = Principled {
+            sheen: 1.0,
+            clearcoat: 1.0,
+            ..Principled::new(Color::new(0.5, 0.2, 0.1))
+        };
+        let rec = record(Vec3::new(0.0, 0.0, 1.0), true);
+        let r_in: Ray = Ray::new(Point3::new(1.0, 0.0, 1.0), Vec3::new(-1.0, 0.0, -1.0));
+
+        for _ in 0..500 {
+            if let Some(out) = m.scatter(&r_in, &rec) {
+                assert!(out.scattered.direction().z() > 0.0);
+                for a in 0..3 {
+                    assert!(out.attenuation[a].is_finite() && out.attenuation[a] >= 0.0);
+                }
+            }
+        }
+    }
+}
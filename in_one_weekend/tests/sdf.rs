@@ -0,0 +1,90 @@
+#[cfg(test)]
+mod tests {
+    use in_one_weekend::aabb::Aabb;
+    use in_one_weekend::hittable::{HitRecord, Hittable};
+    use in_one_weekend::material::{Lambertian, Material};
+    use in_one_weekend::ray::Ray;
+    use in_one_weekend::sdf::*;
+    use in_one_weekend::vec3::*;
+
+    use std::sync::Arc;
+
+    fn material() -> Arc<dyn Material + Sync + Send> {
+        Arc::new(Lambertian::new(Color::new(0.0, 0.0, 0.0)))
+    }
+
+    fn big_bounds() -> Aabb {
+        Aabb::new(
+            Point3::new(-10.0, -10.0, -10.0),
+            Point3::new(10.0, 10.0, 10.0),
+        )
+    }
+
+    #[test]
+    fn shape_distance_test() {
+        let sphere = SdfSphere::new(Point3::new(0.0, 0.0, 0.0), 1.0);
+        assert_eq!(sphere.distance(Point3::new(3.0, 0.0, 0.0)), 2.0);
+        assert_eq!(sphere.distance(Point3::new(0.0, 0.0, 0.0)), -1.0);
+
+        let b = SdfBox::new(Point3::new(0.0, 0.0, 0.0), Vec3::new(1.0, 2.0, 3.0));
+        assert_eq!(b.distance(Point3::new(0.0, 5.0, 0.0)), 3.0);
+        assert_eq!(b.distance(Point3::new(0.5, 0.0, 0.0)), -0.5);
+
+        let torus = SdfTorus::new(Point3::new(0.0, 0.0, 0.0), 2.0, 0.5);
+        assert_eq!(torus.distance(Point3::new(2.0, 0.0, 0.0)), -0.5);
+        assert_eq!(torus.distance(Point3::new(0.0, 0.0, 0.0)), 1.5);
+    }
+
+    #[test]
+    fn operator_test() {
+        let a: Arc<dyn Sdf + Sync + Send> =
+            Arc::new(SdfSphere::new(Point3::new(-1.0, 0.0, 0.0), 1.0));
+        let b: Arc<dyn Sdf + Sync + Send> =
+            Arc::new(SdfSphere::new(Point3::new(1.0, 0.0, 0.0), 1.0));
+
+        // far from the seam the blend matches a hard union
+        let blend = SmoothUnion::new(Arc::clone(&a), Arc::clone(&b), 0.5);
+        assert_eq!(blend.distance(Point3::new(-3.0, 0.0, 0.0)), 1.0);
+        // at the seam material is added
+        assert!(
+            blend.distance(Point3::new(0.0, 1.0, 0.0)) < a.distance(Point3::new(0.0, 1.0, 0.0))
+        );
+
+        let carved = SmoothSubtraction::new(Arc::clone(&a), Arc::clone(&b), 0.0);
+        assert_eq!(carved.distance(Point3::new(0.5, 0.0, 0.0)), 0.5);
+
+        let repeated = Repeat::new(Arc::clone(&a), Vec3::new(4.0, 0.0, 0.0));
+        assert_eq!(
+            repeated.distance(Point3::new(7.0, 0.0, 0.0)),
+            a.distance(Point3::new(-1.0, 0.0, 0.0))
+        );
+
+        let untwisted = Twist::new(Arc::clone(&a), 0.0);
+        assert_eq!(
+            untwisted.distance(Point3::new(0.3, 0.7, 0.1)),
+            a.distance(Point3::new(0.3, 0.7, 0.1))
+        );
+    }
+
+    #[test]
+    fn sphere_trace_test() {
+        let sdf: Arc<dyn Sdf + Sync + Send> =
+            Arc::new(SdfSphere::new(Point3::new(0.0, 0.0, 0.0), 1.0));
+        let object = SdfHittable::new(sdf, big_bounds(), material());
+        let r: Ray = Ray::new(Point3::new(-5.0, 0.0, 0.0), Vec3::new(2.0, 0.0, 0.0));
+
+        let hit: HitRecord = object.hit(&r, 0.001, f64::INFINITY).unwrap();
+        assert!((hit.t - 2.0).abs() < 1e-4);
+        assert!(hit.front_face);
+        assert!((hit.normal - Vec3::new(-1.0, 0.0, 0.0)).length() < 1e-4);
+
+        // continuing from the surface finds the far side, not the same point
+        let inside: Ray = Ray::new(hit.p, Vec3::new(1.0, 0.0, 0.0));
+        let exit: HitRecord = object.hit(&inside, 0.001, f64::INFINITY).unwrap();
+        assert!((exit.p - Point3::new(1.0, 0.0, 0.0)).length() < 1e-4);
+        assert!(!exit.front_face);
+
+        let miss: Ray = Ray::new(Point3::new(-5.0, 2.0, 0.0), Vec3::new(1.0, 0.0, 0.0));
+        assert!(object.hit(&miss, 0.001, f64::INFINITY).is_none());
+    }
+}
@@ -0,0 +1,125 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common;
+    use in_one_weekend::hittable_list::HittableList;
+    use in_one_weekend::material::{Dielectric, Material};
+    use in_one_weekend::ray::Ray;
+    use in_one_weekend::scenes::{ray_color, ray_color_spectral};
+    use in_one_weekend::spectral::*;
+    use in_one_weekend::vec3::*;
+
+    // film response averaged over stratified hero wavelengths
+    fn round_trip(c: Color, terminate: bool) -> Color {
+        let n = 4000;
+        let mut sum = Color::new(0.0, 0.0, 0.0);
+        for i in 0..n {
+            let mut lambda = SampledWavelengths::sample_uniform((i as f64 + 0.5) / n as f64);
+            if terminate {
+                lambda.terminate_secondary();
+            }
+            sum += lambda.to_rgb(&rgb_to_spectrum(c, &lambda));
+        }
+        sum / n as f64
+    }
+
+    #[test]
+    fn hero_wavelengths_test() {
+        let mut lambda = SampledWavelengths::sample_uniform(0.9);
+        for l in lambda.lambda {
+            assert!((LAMBDA_MIN..LAMBDA_MAX).contains(&l));
+        }
+        let spacing = (LAMBDA_MAX - LAMBDA_MIN) / SPECTRUM_SAMPLES as f64;
+        assert!((lambda.lambda[2] - lambda.lambda[1] - spacing).abs() < 1e-9);
+
+        assert!(!lambda.secondary_terminated());
+        lambda.terminate_secondary();
+        assert!(lambda.secondary_terminated());
+        assert_eq!(lambda.pdf[1], 0.0);
+    }
+
+    #[test]
+    fn white_and_gray_round_trip_test() {
+        let white = Color::new(1.0, 1.0, 1.0);
+        assert!((round_trip(white, false) - white).length() < 1e-6);
+        // dropping the secondary wavelengths keeps the estimate unbiased
+        assert!((round_trip(white, true) - white).length() < 1e-3);
+        let gray = Color::new(0.3, 0.3, 0.3);
+        assert!((round_trip(gray, false) - gray).length() < 1e-6);
+    }
+
+    #[test]
+    fn primaries_round_trip_test() {
+        for c in [
+            Color::new(1.0, 0.0, 0.0),
+            Color::new(0.0, 1.0, 0.0),
+            Color::new(0.0, 0.0, 1.0),
+            Color::new(0.2, 0.5, 0.9),
+        ] {
+            assert!((round_trip(c, false) - c).length() < 0.1);
+        }
+    }
+
+    #[test]
+    fn basis_is_a_valid_reflectance_test() {
+        let mut l = LAMBDA_MIN;
+        while l < LAMBDA_MAX {
+            let b = rgb_basis(l);
+            assert!((b.x() + b.y() + b.z() - 1.0).abs() < 1e-12);
+            assert!(b.x() >= 0.0 && b.y() >= 0.0 && b.z() >= 0.0);
+            l += 5.0;
+        }
+    }
+
+    #[test]
+    fn dispersion_test() {
+        // catalogue values for bk7 at the fraunhofer d line
+        assert!((Dispersion::bk7().ior(587.6) - 1.5168).abs() < 1e-3);
+        let cauchy = Dispersion::Cauchy { a: 1.5, b: 0.004 };
+        assert!((cauchy.ior(500.0) - 1.516).abs() < 1e-9);
+        // blue bends more than red
+        for d in [
+            Dispersion::bk7(),
+            Dispersion::dense_flint(),
+            Dispersion::fused_silica(),
+            cauchy,
+        ] {
+            assert!(d.ior(450.0) > d.ior(650.0));
+        }
+    }
+
+    #[test]
+    fn dielectric_disperses_test() {
+        let glass = Dielectric::with_dispersion(Dispersion::dense_flint());
+        assert!(glass.dispersive());
+        assert!(!Dielectric::new(1.5).dispersive());
+
+        let rec = common::record(Vec3::new(0.0, 1.0, 0.0), true);
+        let refracted_x = |lambda: f64| {
+            let mut r_in: Ray = Ray::new(Point3::new(-1.0, 1.0, 0.0), Vec3::new(1.0, -1.0, 0.0));
+            r_in.wavelength = Some(lambda);
+            // retry until fresnel picks refraction
+            loop {
+                let out = glass.scatter(&r_in, &rec).unwrap();
+                let d = unit_vector(out.scattered.direction());
+                if d.y() < 0.0 {
+                    return d.x();
+                }
+            }
+        };
+        assert!(refracted_x(450.0) < refracted_x(650.0));
+    }
+
+    #[test]
+    fn spectral_sky_matches_rgb_test() {
+        let world = HittableList::new();
+        let r: Ray = Ray::new(Point3::new(0.0, 0.0, 0.0), Vec3::new(0.0, 1.0, 1.0));
+        let n = 4000;
+        let mut sum = Color::new(0.0, 0.0, 0.0);
+        for _ in 0..n {
+            sum += ray_color_spectral(&r, &world, 10);
+        }
+        assert!((sum / n as f64 - ray_color(&r, &world, 10)).length() < 0.05);
+    }
+}
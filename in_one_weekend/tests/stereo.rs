@@ -0,0 +1,112 @@
+#[cfg(test)]
+mod tests {
+    use in_one_weekend::camera::Camera;
+    use in_one_weekend::ray::Ray;
+    use in_one_weekend::stereo::*;
+    use in_one_weekend::vec3::*;
+
+    // looking down -z from the origin, eyes 0.064 apart converging at 2
+    fn rig(mode: StereoMode, layout: StereoLayout) -> StereoRig {
+        StereoRig::perspective(
+            Point3::new(0.0, 0.0, 0.0),
+            Point3::new(0.0, 0.0, -1.0),
+            Vec3::new(0.0, 1.0, 0.0),
+            60.0,
+            1.0,
+            0.064,
+            2.0,
+            mode,
+            layout,
+        )
+    }
+
+    // where a ray crosses the plane z = -distance
+    fn at_depth(r: &Ray, distance: f64) -> Point3 {
+        let t = (-distance - r.origin().z()) / r.direction().z();
+        r.at(t)
+    }
+
+    #[test]
+    fn layouts_split_image_test() {
+        let sbs = StereoLayout::SideBySide;
+        assert_eq!(sbs.split(0.25, 0.3), (Eye::Left, 0.5, 0.3));
+        assert_eq!(sbs.split(0.75, 0.3), (Eye::Right, 0.5, 0.3));
+        assert_eq!(sbs.aspect_ratio(1.5), 3.0);
+
+        // the left eye goes on top, t counting up from the bottom
+        let ou = StereoLayout::OverUnder;
+        assert_eq!(ou.split(0.3, 0.75), (Eye::Left, 0.3, 0.5));
+        assert_eq!(ou.split(0.3, 0.25), (Eye::Right, 0.3, 0.5));
+        assert_eq!(ou.aspect_ratio(2.0), 1.0);
+    }
+
+    #[test]
+    fn eyes_are_interocular_apart_test() {
+        for mode in [StereoMode::Parallel, StereoMode::ToeIn] {
+            let cam = rig(mode, StereoLayout::SideBySide);
+            let left = cam.get_ray(0.25, 0.5);
+            let right = cam.get_ray(0.75, 0.5);
+            assert!((left.origin() - Point3::new(-0.032, 0.0, 0.0)).length() < 1e-12);
+            assert!((right.origin() - Point3::new(0.032, 0.0, 0.0)).length() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn parallel_converges_without_toe_in_test() {
+        let cam = rig(StereoMode::Parallel, StereoLayout::SideBySide);
+        let left = cam.left.get_ray(0.0, 0.0);
+        let right = cam.right.get_ray(0.0, 0.0);
+        // the eyes share one image plane, so their rays differ only
+        // horizontally
+        let skew = left.direction() - right.direction();
+        assert!(skew.y().abs() < 1e-12 && skew.z().abs() < 1e-12);
+
+        // matching film points meet on the convergence plane, with no
+        // vertical disparity anywhere
+        let (pl, pr) = (at_depth(&left, 2.0), at_depth(&right, 2.0));
+        assert!((pl - pr).length() < 1e-9);
+        let (fl, fr) = (at_depth(&left, 10.0), at_depth(&right, 10.0));
+        assert!((fl.y() - fr.y()).abs() < 1e-9);
+        // beyond it the rays have crossed over
+        assert!(fl.x() > fr.x());
+    }
+
+    #[test]
+    fn toe_in_meets_at_convergence_test() {
+        let cam = rig(StereoMode::ToeIn, StereoLayout::OverUnder);
+        let left = cam.get_ray(0.5, 0.75);
+        let right = cam.get_ray(0.5, 0.25);
+        let (pl, pr) = (at_depth(&left, 2.0), at_depth(&right, 2.0));
+        assert!((pl - Point3::new(0.0, 0.0, -2.0)).length() < 1e-9);
+        assert!((pr - Point3::new(0.0, 0.0, -2.0)).length() < 1e-9);
+    }
+
+    #[test]
+    fn ods_rays_are_tangent_test() {
+        let cam = StereoRig::ods(
+            Point3::new(0.0, 1.0, 0.0),
+            Point3::new(0.0, 1.0, -1.0),
+            Vec3::new(0.0, 1.0, 0.0),
+            0.064,
+            StereoLayout::OverUnder,
+        );
+        for &s in [0.1, 0.3, 0.5, 0.8].iter() {
+            let left = cam.left.get_ray(s, 0.5);
+            let right = cam.right.get_ray(s, 0.5);
+            assert_eq!(left.direction(), right.direction());
+            // on the horizon the eyes sit interocular apart across the
+            // viewing direction
+            let baseline = right.origin() - left.origin();
+            assert!((baseline.length() - 0.064).abs() < 1e-12);
+            assert!(dot(&baseline, &left.direction()).abs() < 1e-12);
+        }
+        // looking forward the right eye is to the right
+        let right = cam.right.get_ray(0.5, 0.5);
+        assert!(right.origin().x() > 0.0);
+
+        // the eyes merge at the poles
+        let top_l = cam.left.get_ray(0.3, 1.0);
+        let top_r = cam.right.get_ray(0.3, 1.0);
+        assert!((top_l.origin() - top_r.origin()).length() < 1e-12);
+    }
+}
@@ -0,0 +1,83 @@
+#[cfg(test)]
+mod tests {
+    use in_one_weekend::hittable::{HitRecord, Hittable};
+    use in_one_weekend::material::{Lambertian, Material};
+    use in_one_weekend::ray::Ray;
+    use in_one_weekend::sphere::Sphere;
+    use in_one_weekend::subsurface::Subsurface;
+    use in_one_weekend::vec3::*;
+
+    use std::sync::Arc;
+
+    fn unit_sphere() -> Arc<dyn Hittable + Sync + Send> {
+        let m: Arc<dyn Material + Sync + Send> =
+            Arc::new(Lambertian::new(Color::new(0.0, 0.0, 0.0)));
+        Arc::new(Sphere::new(Point3::new(0.0, 0.0, 0.0), 1.0, m))
+    }
+
+    // ray straight down onto the top of the unit sphere
+    fn top_hit(m: &Subsurface) -> (Ray, HitRecord) {
+        let r_in: Ray = Ray::new(Point3::new(0.0, 2.0, 0.0), Vec3::new(0.0, -1.0, 0.0));
+        let rec = m.boundary.hit(&r_in, 0.001, f64::INFINITY).unwrap();
+        (r_in, rec)
+    }
+
+    fn average(m: &Subsurface, n: usize) -> Color {
+        let (r_in, rec) = top_hit(m);
+        let mut total = Color::new(0.0, 0.0, 0.0);
+        for _ in 0..n {
+            if let Some(out) = m.scatter(&r_in, &rec) {
+                total += out.attenuation;
+            }
+        }
+        total / n as f64
+    }
+
+    #[test]
+    fn exits_through_the_surface_test() {
+        let white = Color::new(1.0, 1.0, 1.0);
+        let m = Subsurface::new(unit_sphere(), white, 0.3 * white, 1.3);
+        let (r_in, rec) = top_hit(&m);
+        for _ in 0..1000 {
+            if let Some(out) = m.scatter(&r_in, &rec) {
+                let p = out.scattered.origin();
+                assert!((p.length() - 1.0).abs() < 1e-6);
+                assert!(dot(&out.scattered.direction(), &p) > 0.0);
+            }
+        }
+    }
+
+    #[test]
+    fn white_medium_conserves_energy_test() {
+        // nothing is absorbed, so almost every path leaves with full weight
+        let white = Color::new(1.0, 1.0, 1.0);
+        let m = Subsurface::new(unit_sphere(), white, 0.5 * white, 1.0);
+        let mean = average(&m, 5000);
+        assert!(mean.x() > 0.95 && mean.x() < 1.05);
+        assert!((mean.x() - mean.z()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn albedo_darkens_test() {
+        let mfp = Color::new(0.2, 0.2, 0.2);
+        let bright = Subsurface::new(unit_sphere(), Color::new(0.99, 0.99, 0.99), mfp, 1.3);
+        let dark = Subsurface::new(unit_sphere(), Color::new(0.8, 0.8, 0.8), mfp, 1.3);
+        assert!(average(&bright, 3000).x() > average(&dark, 3000).x() + 0.1);
+    }
+
+    #[test]
+    fn longer_mean_free_path_loses_less_test() {
+        // red light travels further between collisions, so it is absorbed
+        // at fewer of them and comes out brighter, as with skin
+        let m = Subsurface::new(
+            unit_sphere(),
+            Color::new(0.9, 0.9, 0.9),
+            Color::new(2.0, 0.2, 0.05),
+            1.4,
+        );
+        let mean = average(&m, 5000);
+        assert!(mean.x() > mean.y());
+        assert!(mean.y() > mean.z());
+        assert!(mean.z() >= 0.0);
+    }
+}
@@ -0,0 +1,91 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common;
+    use in_one_weekend::hittable::HitRecord;
+    use in_one_weekend::image::Image;
+    use in_one_weekend::material::{Lambertian, Material, Mix};
+    use in_one_weekend::ray::Ray;
+    use in_one_weekend::texture::*;
+    use in_one_weekend::vec3::*;
+
+    use std::sync::Arc;
+
+    fn record(p: Point3) -> HitRecord {
+        HitRecord {
+            p,
+            u: 0.25,
+            v: 0.75,
+            ..common::record(Vec3::new(0.0, 1.0, 0.0), true)
+        }
+    }
+
+    #[test]
+    fn textures_test() {
+        let p = Point3::new(0.5, 0.5, 0.5);
+        let red = Color::new(1.0, 0.0, 0.0);
+        let blue = Color::new(0.0, 0.0, 1.0);
+        assert_eq!(SolidColor::new(red).value(0.0, 0.0, &p), red);
+
+        let checker = Checker::from_colors(red, blue, 1.0);
+        assert_eq!(checker.value(0.0, 0.0, &p), red);
+        assert_eq!(checker.value(0.0, 0.0, &Point3::new(1.5, 0.5, 0.5)), blue);
+        assert_eq!(checker.value(0.0, 0.0, &Point3::new(-0.5, 0.5, 0.5)), blue);
+
+        // top left pixel is at high v
+        let image = Image::new(2, 2, vec![red, blue, blue, red]);
+        let texture = ImageTexture::new(image);
+        assert_eq!(texture.value(0.1, 0.9, &p), red);
+        assert_eq!(texture.value(0.9, 0.9, &p), blue);
+
+        let marble = NoiseTexture::new(4.0, Color::new(1.0, 1.0, 1.0));
+        let c = marble.value(0.0, 0.0, &p);
+        assert!((0.0..=1.0).contains(&c.x()));
+    }
+
+    #[test]
+    fn constant_mix_test() {
+        let a = Color::new(1.0, 0.0, 0.0);
+        let b = Color::new(0.0, 1.0, 0.0);
+        let first: Arc<dyn Material + Sync + Send> = Arc::new(Lambertian::new(a));
+        let second: Arc<dyn Material + Sync + Send> = Arc::new(Lambertian::new(b));
+        let rec = record(Point3::new(0.0, 0.0, 0.0));
+        let r_in: Ray = Ray::new(Point3::new(0.0, 1.0, 0.0), Vec3::new(0.0, -1.0, 0.0));
+
+        let pick = |w: f64| Mix::new(Arc::clone(&first), Arc::clone(&second), w);
+        assert_eq!(pick(0.0).scatter(&r_in, &rec).unwrap().attenuation, a);
+        assert_eq!(pick(1.0).scatter(&r_in, &rec).unwrap().attenuation, b);
+
+        let mix = pick(0.3);
+        let n = 20000;
+        let seconds = (0..n)
+            .filter(|_| mix.scatter(&r_in, &rec).unwrap().attenuation == b)
+            .count();
+        assert!((seconds as f64 / n as f64 - 0.3).abs() < 0.02);
+    }
+
+    #[test]
+    fn masked_mix_test() {
+        let a = Color::new(1.0, 0.0, 0.0);
+        let b = Color::new(0.0, 1.0, 0.0);
+        let first: Arc<dyn Material + Sync + Send> = Arc::new(Lambertian::new(a));
+        let second: Arc<dyn Material + Sync + Send> = Arc::new(Lambertian::new(b));
+        let mask: Arc<dyn Texture + Sync + Send> = Arc::new(Checker::from_colors(
+            Color::new(0.0, 0.0, 0.0),
+            Color::new(1.0, 1.0, 1.0),
+            1.0,
+        ));
+        let mix = Mix::with_mask(first, second, mask);
+        let r_in: Ray = Ray::new(Point3::new(0.0, 1.0, 0.0), Vec3::new(0.0, -1.0, 0.0));
+
+        let black = record(Point3::new(0.5, 0.0, 0.5));
+        let white = record(Point3::new(1.5, 0.0, 0.5));
+        assert_eq!(mix.weight_at(&black), 0.0);
+        assert!((mix.weight_at(&white) - 1.0).abs() < 1e-12);
+        for _ in 0..20 {
+            assert_eq!(mix.scatter(&r_in, &black).unwrap().attenuation, a);
+            assert_eq!(mix.scatter(&r_in, &white).unwrap().attenuation, b);
+        }
+    }
+}
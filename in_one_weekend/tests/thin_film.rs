@@ -0,0 +1,88 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common;
+    use in_one_weekend::fresnel::*;
+    use in_one_weekend::material::{Conductor, Dielectric, Material};
+    use in_one_weekend::ray::Ray;
+    use in_one_weekend::thin_film::*;
+    use in_one_weekend::vec3::*;
+
+    #[test]
+    fn vanishing_film_test() {
+        // a film of no thickness, or matching the outside medium, leaves
+        // the bare substrate
+        for film in [ThinFilm::new(0.0, 1.7), ThinFilm::new(300.0, 1.0)] {
+            for cos in [0.1, 0.5, 0.9, 1.0] {
+                let r = film.reflectance_at(cos, 1.0, 1.5, 0.0, 550.0);
+                assert!((r - fresnel_dielectric(cos, 1.5)).abs() < 1e-9, "{r}");
+                let r = film.reflectance_at(cos, 1.0, 0.2, 3.9, 550.0);
+                assert!((r - fresnel_complex(cos, 0.2, 3.9)).abs() < 1e-9, "{r}");
+            }
+        }
+    }
+
+    #[test]
+    fn quarter_wave_coating_test() {
+        // the classic anti reflection coating cancels reflection entirely
+        let n_glass: f64 = 1.5;
+        let n_film = n_glass.sqrt();
+        let film = ThinFilm::new(550.0 / (4.0 * n_film), n_film);
+        assert!(film.reflectance_at(1.0, 1.0, n_glass, 0.0, 550.0) < 1e-12);
+        // but only at its design wavelength
+        assert!(film.reflectance_at(1.0, 1.0, n_glass, 0.0, 450.0) > 1e-4);
+    }
+
+    #[test]
+    fn total_internal_reflection_test() {
+        let film = ThinFilm::new(200.0, 1.33);
+        assert!((film.reflectance_at(0.1, 1.5, 1.0, 0.0, 550.0) - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn iridescence_test() {
+        // thickness shifts which channel is reflected most
+        let white = Color::new(1.0, 1.0, 1.0);
+        let thin = ThinFilm::new(250.0, 1.33).reflectance(1.0, 1.0, white, 0.0 * white, None);
+        let thick = ThinFilm::new(350.0, 1.33).reflectance(1.0, 1.0, white, 0.0 * white, None);
+        assert!((thin - thick).length() > 0.01);
+        let dominant = |c: Color| (0..3).max_by(|&a, &b| c[a].total_cmp(&c[b])).unwrap();
+        assert_ne!(dominant(thin), dominant(thick));
+
+        // with a wavelength every channel gets the reflectance at it
+        let film = ThinFilm::new(300.0, 1.33);
+        let spectral = film.reflectance(0.7, 1.0, 1.5 * white, 0.0 * white, Some(450.0));
+        let expected = film.reflectance_at(0.7, 1.0, 1.5, 0.0, 450.0);
+        assert_eq!(spectral, Color::new(expected, expected, expected));
+        let rgb = film.reflectance(0.7, 1.0, 1.5 * white, 0.0 * white, None);
+        assert!((rgb.z() - expected).abs() < 1e-12);
+    }
+
+    #[test]
+    fn coated_materials_test() {
+        let rec = common::record(Vec3::new(0.0, 1.0, 0.0), true);
+        let r_in: Ray = Ray::new(Point3::new(0.0, 1.0, 0.0), Vec3::new(0.0, -1.0, 0.0));
+
+        let film = ThinFilm::new(400.0, 1.4);
+        let metal = Conductor::with_thin_film(ComplexIor::silver(), film);
+        assert!(metal.dispersive());
+        let out = metal.scatter(&r_in, &rec).unwrap();
+        assert_eq!(
+            out.attenuation,
+            film.reflectance_conductor(1.0, &ComplexIor::silver(), None)
+        );
+        let rough = ConductorFresnel::ThinFilm(ComplexIor::silver(), film);
+        assert_eq!(rough.reflectance(1.0), out.attenuation);
+
+        // a lossless bubble reflects and transmits everything on average
+        let bubble = Dielectric::bubble(ThinFilm::new(300.0, 1.33));
+        assert!(bubble.dispersive());
+        let n = 4000;
+        let mut total = Color::new(0.0, 0.0, 0.0);
+        for _ in 0..n {
+            total += bubble.scatter(&r_in, &rec).unwrap().attenuation;
+        }
+        assert!((total / n as f64 - Color::new(1.0, 1.0, 1.0)).length() < 0.05);
+    }
+}